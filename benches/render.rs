@@ -0,0 +1,115 @@
+//! Rendering benchmarks.
+//!
+//! Besides the timings from criterion, the number of allocations made while
+//! drawing a large log is printed for each way of rendering a box, so that
+//! building the box as a String can be compared with streaming it.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Write as _;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bauxite::{AnsiColorCode, BoxBuilder};
+use criterion::{black_box, criterion_group, Criterion};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// A log of `lines` short lines, like the output of a busy service
+fn log(lines: usize) -> String {
+    (0..lines)
+        .map(|line| {
+            format!(
+                "{:>6} INFO request handled in {} ms by worker {}",
+                line,
+                line % 97,
+                line % 8
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn boxed_log(lines: usize) -> BoxBuilder {
+    BoxBuilder::from(&log(lines))
+        .max_width(120)
+        .title("service.log")
+        .color(AnsiColorCode::Blue)
+}
+
+fn allocations(render: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    render();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn report_allocations() {
+    let boxed = boxed_log(2000);
+    let mut reused = String::with_capacity(boxed.to_string().len());
+    println!("allocations drawing a box around 2000 lines:");
+    println!(
+        "  to_string             {:>8}",
+        allocations(|| {
+            black_box(boxed.to_string());
+        })
+    );
+    println!(
+        "  render_to String      {:>8}",
+        allocations(|| {
+            reused.clear();
+            boxed.render_to(&mut reused).unwrap();
+        })
+    );
+    println!(
+        "  write_to io::sink     {:>8}",
+        allocations(|| boxed.write_to(&mut io::sink()).unwrap())
+    );
+    println!(
+        "  write! with Display   {:>8}",
+        allocations(|| {
+            reused.clear();
+            write!(reused, "{}", boxed).unwrap();
+        })
+    );
+}
+
+fn render(c: &mut Criterion) {
+    let boxed = boxed_log(2000);
+    let mut reused = String::new();
+
+    let mut group = c.benchmark_group("render 2000 lines");
+    group.bench_function("to_string", |b| b.iter(|| black_box(boxed.to_string())));
+    group.bench_function("render_to String", |b| {
+        b.iter(|| {
+            reused.clear();
+            boxed.render_to(&mut reused).unwrap();
+            black_box(&reused);
+        })
+    });
+    group.bench_function("write_to io::sink", |b| {
+        b.iter(|| boxed.write_to(&mut io::sink()).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, render);
+
+fn main() {
+    report_allocations();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+}
@@ -0,0 +1,208 @@
+/// ANSI escape sequences embedded in the message
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+const ESCAPE: char = '\x1B';
+pub const RESET_CODE: &str = "\x1B[0m";
+
+/// A piece of text that is never split: an escape sequence or a grapheme
+pub enum Segment<'a> {
+    Escape(&'a str),
+    Grapheme(&'a str),
+}
+
+/// Length in bytes of the escape sequence at the start of `text`, if there is one.
+///
+/// Control sequences (CSI) run from `ESC [` to a final byte between `@` and `~`.
+/// Operating system commands (OSC) run from `ESC ]` to a BEL or `ESC \`.
+/// Any other escape is taken to be `ESC` and one more character.
+/// Sequences cut off by the end of the text run to the end of the text.
+pub fn escape_len(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    if chars.next() != Some((0, ESCAPE)) {
+        return None;
+    }
+    match chars.next() {
+        Some((_, '[')) => chars
+            .find(|(_, c)| ('@'..='~').contains(c))
+            .map_or(Some(text.len()), |(index, c)| Some(index + c.len_utf8())),
+        Some((_, ']')) => {
+            let mut after_escape = false;
+            for (index, c) in chars {
+                if c == '\x07' || (after_escape && c == '\\') {
+                    return Some(index + c.len_utf8());
+                }
+                after_escape = c == ESCAPE;
+            }
+            Some(text.len())
+        }
+        Some((index, c)) => Some(index + c.len_utf8()),
+        None => Some(text.len()),
+    }
+}
+
+/// Split text into escape sequences and graphemes, each with its byte index
+pub fn segments(text: &str) -> Vec<(usize, Segment<'_>)> {
+    let mut segments = Vec::new();
+    let mut index = 0;
+    while index < text.len() {
+        let rest = &text[index..];
+        let segment = match escape_len(rest) {
+            Some(len) => Segment::Escape(&rest[..len]),
+            None => Segment::Grapheme(rest.graphemes(true).next().unwrap_or(rest)),
+        };
+        let len = match segment {
+            Segment::Escape(escape) => escape.len(),
+            Segment::Grapheme(grapheme) => grapheme.len(),
+        };
+        segments.push((index, segment));
+        index += len;
+    }
+    segments
+}
+
+/// Byte ranges of the escape sequences in a string
+pub fn escape_ranges(text: &str) -> Vec<Range<usize>> {
+    segments(text)
+        .into_iter()
+        .filter_map(|(index, segment)| match segment {
+            Segment::Escape(escape) => Some(index..index + escape.len()),
+            Segment::Grapheme(_) => None,
+        })
+        .collect()
+}
+
+/// Remove the escape sequences from a string, leaving the visible text
+pub fn strip_escapes(text: &str) -> String {
+    segments(text)
+        .into_iter()
+        .filter_map(|(_, segment)| match segment {
+            Segment::Escape(_) => None,
+            Segment::Grapheme(grapheme) => Some(grapheme),
+        })
+        .collect()
+}
+
+/// Carry the styles still set at the end of each line on to the next line.
+///
+/// A line that ends with a style set gets a reset, so the style doesn't run
+/// into the lines of the box, and the next line sets the style again.
+pub fn carry_styles<'a>(lines: impl IntoIterator<Item = &'a mut String>) {
+    let mut active: Vec<String> = Vec::new();
+    for line in lines {
+        if active.is_empty() && !line.contains(ESCAPE) {
+            continue;
+        }
+        let opened = active.concat();
+        for (_, segment) in segments(line) {
+            if let Segment::Escape(escape) = segment {
+                update_styles(&mut active, escape);
+            }
+        }
+        if !opened.is_empty() {
+            line.insert_str(0, &opened);
+        }
+        if !active.is_empty() {
+            line.push_str(RESET_CODE);
+        }
+    }
+}
+
+/// The parameters of an SGR sequence like `ESC [ 1;31 m`, if `escape` is one
+fn sgr_parameters(escape: &str) -> Option<&str> {
+    escape
+        .strip_prefix("\x1B[")
+        .and_then(|escape| escape.strip_suffix('m'))
+}
+
+/// The parameters `escape` sets after turning every style off, if it resets them.
+///
+/// `ESC [ m` and `ESC [ 0 m` set nothing after the reset, `ESC [ 0;31 m` sets `31`.
+pub fn reset_parameters(escape: &str) -> Option<&str> {
+    let parameters = sgr_parameters(escape)?;
+    if parameters.is_empty() || parameters == "0" {
+        return Some("");
+    }
+    parameters.strip_prefix("0;")
+}
+
+/// Track the SGR sequences that are in effect after `escape`
+fn update_styles(active: &mut Vec<String>, escape: &str) {
+    if sgr_parameters(escape).is_none() {
+        return;
+    }
+    match reset_parameters(escape) {
+        Some("") => active.clear(),
+        Some(_) => {
+            active.clear();
+            active.push(String::from(escape));
+        }
+        None => active.push(String::from(escape)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_len() {
+        assert_eq!(escape_len("\x1B[1;31mred"), Some(7));
+        assert_eq!(escape_len("\x1B]8;;https://example.com\x07link"), Some(25));
+        assert_eq!(escape_len("\x1B]0;title\x1B\\text"), Some(11));
+        assert_eq!(escape_len("\x1B7text"), Some(2));
+        assert_eq!(escape_len("\x1B[31"), Some(4));
+        assert_eq!(escape_len("text"), None);
+    }
+
+    #[test]
+    fn test_reset_parameters() {
+        assert_eq!(reset_parameters("\x1B[0m"), Some(""));
+        assert_eq!(reset_parameters("\x1B[m"), Some(""));
+        assert_eq!(reset_parameters("\x1B[0;1;32m"), Some("1;32"));
+        assert_eq!(reset_parameters("\x1B[31m"), None);
+        assert_eq!(reset_parameters("\x1B[0K"), None);
+    }
+
+    #[test]
+    fn test_strip_escapes() {
+        assert_eq!(strip_escapes("a\x1B[34mb\x1B[0mc"), "abc");
+        assert_eq!(strip_escapes("a\x1B]8;;https://a-b.c/d\x1B\\b"), "ab");
+        assert_eq!(strip_escapes("a\x1B7b"), "ab");
+        assert_eq!(strip_escapes("abc"), "abc");
+    }
+
+    #[test]
+    fn test_escape_ranges() {
+        assert_eq!(escape_ranges("a\x1B[1mb\x1B[0m"), vec![1..5, 6..10]);
+    }
+
+    #[test]
+    fn test_carry_styles() {
+        let mut lines = vec![
+            String::from("\x1B[1mLorem \x1B[31mipsum"),
+            String::from("dolor\x1B[0m sit"),
+            String::from("amet"),
+        ];
+        carry_styles(lines.iter_mut());
+        assert_eq!(
+            lines,
+            vec![
+                "\x1B[1mLorem \x1B[31mipsum\x1B[0m",
+                "\x1B[1m\x1B[31mdolor\x1B[0m sit",
+                "amet",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_carry_styles_reset_and_set() {
+        let mut lines = vec![String::from("\x1B[1ma\x1B[0;32mb"), String::from("c")];
+        carry_styles(lines.iter_mut());
+        assert_eq!(
+            lines,
+            vec!["\x1B[1ma\x1B[0;32mb\x1B[0m", "\x1B[0;32mc\x1B[0m"]
+        );
+    }
+}
@@ -0,0 +1,18 @@
+/// Text attributes set with SGR codes
+#[derive(Clone, Copy, PartialEq)]
+pub enum Attribute {
+    /// SGR 1, bold or increased intensity
+    Bold,
+    /// SGR 2, faint or decreased intensity
+    Dim,
+    /// SGR 3
+    Italic,
+    /// SGR 4
+    Underline,
+    /// SGR 5, slow blink
+    Blink,
+    /// SGR 7, swap the foreground and background colors
+    Reverse,
+    /// SGR 9, crossed out
+    Strikethrough,
+}
@@ -1,11 +1,14 @@
 pub mod ansi_color_codes;
+pub mod attribute;
 pub mod rgb_color;
 
+use std::fmt;
+
+use crate::ansi::{self, Segment, RESET_CODE};
 use ansi_color_codes::AnsiColorCode;
+use attribute::Attribute;
 use rgb_color::RgbColor;
 
-const RESET_CODE: &'static str = "\x1B[0m";
-
 /// Stores the line color information for the box.
 /// Only one line color type should have a value at a time.
 pub struct LineColor {
@@ -24,16 +27,49 @@ impl LineColor {
         }
     }
 
-    /// Wraps the given text in the color specified by the LineColor struct.
-    pub fn wrap_color(&self, text: String) -> String {
+    /// Constructs a line color from an ANSI color code.
+    pub fn from_ansi(code: AnsiColorCode) -> LineColor {
+        LineColor {
+            ansi: Some(code),
+            ..LineColor::new()
+        }
+    }
+
+    /// Constructs a line color from an RGB color.
+    pub fn from_rgb(rgb: RgbColor) -> LineColor {
+        LineColor {
+            rgb: Some(rgb),
+            ..LineColor::new()
+        }
+    }
+
+    /// Constructs a line color from an 8 bit color code.
+    pub fn from_8(color: u8) -> LineColor {
+        LineColor {
+            color8: Some(color),
+            ..LineColor::new()
+        }
+    }
+
+    /// SGR parameters that set this color as the foreground color.
+    pub fn foreground(&self) -> Option<String> {
+        if let Some(ansi) = &self.ansi {
+            Some(self.color_code(ansi, 0))
+        } else if let Some(rgb) = &self.rgb {
+            Some(self.color_rgb(rgb, "38"))
+        } else {
+            self.color8.map(|color8| self.color_8(color8, "38"))
+        }
+    }
+
+    /// SGR parameters that set this color as the background color.
+    pub fn background(&self) -> Option<String> {
         if let Some(ansi) = &self.ansi {
-            self.color_code(text, &ansi)
+            Some(self.color_code(ansi, 10))
         } else if let Some(rgb) = &self.rgb {
-            self.color_rgb(text, rgb)
-        } else if let Some(color8) = &self.color8 {
-            self.color_8(text, color8)
+            Some(self.color_rgb(rgb, "48"))
         } else {
-            text
+            self.color8.map(|color8| self.color_8(color8, "48"))
         }
     }
 
@@ -42,45 +78,228 @@ impl LineColor {
     /// 8-15 are high intensity colors
     /// 16-231 are defined by 16 + 36 x r + 6 x g + b (0 <= r, g, b <= 5)
     /// 232-255 are grayscale from black to white in 24 steps
-    fn color_8(&self, text: String, color: &u8) -> String {
-        format!("\x1B[38;5;{}m{}{}", color, text, RESET_CODE)
+    fn color_8(&self, color: u8, layer: &str) -> String {
+        format!("{};5;{}", layer, color)
     }
 
     /// Basic RGB colors.
-    fn color_rgb(&self, text: String, rgb: &RgbColor) -> String {
-        format!(
-            "\x1B[38;2;{};{};{}m{}{}",
-            rgb.red, rgb.green, rgb.blue, text, RESET_CODE
-        )
+    fn color_rgb(&self, rgb: &RgbColor, layer: &str) -> String {
+        format!("{};2;{};{};{}", layer, rgb.red, rgb.green, rgb.blue)
     }
 
     /// Simplest ANSI color codes defind by AnsiColorCode enumerated type.
-    fn color_code(&self, text: String, color_code: &AnsiColorCode) -> String {
+    /// Background codes are offset from the foreground codes by 10.
+    fn color_code(&self, color_code: &AnsiColorCode, offset: u8) -> String {
         let color = match color_code {
-            AnsiColorCode::Black => "30",
-            AnsiColorCode::Red => "31",
-            AnsiColorCode::Green => "32",
-            AnsiColorCode::Yellow => "33",
-            AnsiColorCode::Blue => "34",
-            AnsiColorCode::Magenta => "35",
-            AnsiColorCode::Cyan => "36",
-            AnsiColorCode::White => "37",
-            AnsiColorCode::BrightBlack => "90",
-            AnsiColorCode::BrightRed => "91",
-            AnsiColorCode::BrightGreen => "92",
-            AnsiColorCode::BrightYellow => "93",
-            AnsiColorCode::BrightBlue => "94",
-            AnsiColorCode::BrightMagenta => "95",
-            AnsiColorCode::BrightCyan => "96",
-            AnsiColorCode::BrightWhite => "97",
+            AnsiColorCode::Black => 30,
+            AnsiColorCode::Red => 31,
+            AnsiColorCode::Green => 32,
+            AnsiColorCode::Yellow => 33,
+            AnsiColorCode::Blue => 34,
+            AnsiColorCode::Magenta => 35,
+            AnsiColorCode::Cyan => 36,
+            AnsiColorCode::White => 37,
+            AnsiColorCode::BrightBlack => 90,
+            AnsiColorCode::BrightRed => 91,
+            AnsiColorCode::BrightGreen => 92,
+            AnsiColorCode::BrightYellow => 93,
+            AnsiColorCode::BrightBlue => 94,
+            AnsiColorCode::BrightMagenta => 95,
+            AnsiColorCode::BrightCyan => 96,
+            AnsiColorCode::BrightWhite => 97,
+        };
+        (color + offset).to_string()
+    }
+}
+
+/// Stores the colors of each part of the box.
+pub struct BoxColor {
+    /// Foreground color of the lines
+    pub border: LineColor,
+    /// Foreground color of the message
+    pub text: LineColor,
+    /// Color filling the box behind the lines and the message
+    pub background: LineColor,
+    /// Attributes of the lines
+    pub border_attributes: Vec<Attribute>,
+    /// Attributes of the message
+    pub text_attributes: Vec<Attribute>,
+}
+
+impl BoxColor {
+    /// Constructs box colors with no color set for any part of the box.
+    pub fn new() -> BoxColor {
+        BoxColor {
+            border: LineColor::new(),
+            text: LineColor::new(),
+            background: LineColor::new(),
+            border_attributes: Vec::new(),
+            text_attributes: Vec::new(),
+        }
+    }
+
+    /// The codes of each part of the box, to write a whole box with.
+    pub fn styles(&self) -> Styles {
+        Styles {
+            border: self.border_code(),
+            text: self.text_code(),
+        }
+    }
+
+    fn border_code(&self) -> Option<String> {
+        self.code(&self.border_attributes, self.border.foreground())
+    }
+
+    fn text_code(&self) -> Option<String> {
+        self.code(&self.text_attributes, self.text.foreground())
+    }
+
+    /// The escape code that turns on the attributes, foreground and background,
+    /// or nothing when there are none
+    fn code(&self, attributes: &[Attribute], foreground: Option<String>) -> Option<String> {
+        let codes = attributes
+            .iter()
+            .map(|attribute| attribute_code(attribute).to_string())
+            .chain(foreground)
+            .chain(self.background.background())
+            .collect::<Vec<String>>();
+        if codes.is_empty() {
+            return None;
+        }
+        Some(format!("\x1B[{}m", codes.join(";")))
+    }
+}
+
+/// The escape codes of each part of the box, worked out once for the whole box.
+///
+/// Every style is turned off again with the reset code at the end of each part,
+/// so nothing leaks into whatever is printed next. Resets already in the
+/// message, such as those of colored words, turn the text style back on straight after.
+pub struct Styles {
+    border: Option<String>,
+    text: Option<String>,
+}
+
+impl Styles {
+    /// Write the part of the box that `draw` writes in the border and background colors
+    pub fn write_border<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        draw: impl FnOnce(&mut W) -> fmt::Result,
+    ) -> fmt::Result {
+        write_styled(out, &self.border, draw)
+    }
+
+    /// Write the part of the box that `draw` writes in the text and background colors
+    pub fn write_text<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        draw: impl FnOnce(&mut W) -> fmt::Result,
+    ) -> fmt::Result {
+        write_styled(out, &self.text, draw)
+    }
+
+    /// Write text from the message inside [write_text](#method.write_text),
+    /// turning the text style back on after every reset in it
+    pub fn write_message<W: fmt::Write>(&self, out: &mut W, text: &str) -> fmt::Result {
+        let code = match &self.text {
+            Some(code) if text.contains('\x1B') => code,
+            _ => return out.write_str(text),
         };
-        format!("\x1B[{}m{}{}", color, text, RESET_CODE)
+        for (_, segment) in ansi::segments(text) {
+            match segment {
+                Segment::Grapheme(grapheme) => out.write_str(grapheme)?,
+                Segment::Escape(escape) => {
+                    out.write_str(escape)?;
+                    if let Some(parameters) = ansi::reset_parameters(escape) {
+                        // the styles set along with the reset go on top of the text style
+                        out.write_str(code)?;
+                        if !parameters.is_empty() {
+                            write!(out, "\x1B[{}m", parameters)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_styled<W: fmt::Write>(
+    out: &mut W,
+    code: &Option<String>,
+    draw: impl FnOnce(&mut W) -> fmt::Result,
+) -> fmt::Result {
+    match code {
+        Some(code) => {
+            out.write_str(code)?;
+            draw(out)?;
+            out.write_str(RESET_CODE)
+        }
+        None => draw(out),
+    }
+}
+
+/// SGR code that turns on a text attribute
+fn attribute_code(attribute: &Attribute) -> u8 {
+    match attribute {
+        Attribute::Bold => 1,
+        Attribute::Dim => 2,
+        Attribute::Italic => 3,
+        Attribute::Underline => 4,
+        Attribute::Blink => 5,
+        Attribute::Reverse => 7,
+        Attribute::Strikethrough => 9,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fmt::Write;
+
+    /// Text written in the border style
+    fn border(color: &BoxColor, text: &str) -> String {
+        let mut out = String::new();
+        let styles = color.styles();
+        styles
+            .write_border(&mut out, |out| out.write_str(text))
+            .unwrap();
+        out
+    }
+
+    /// Message text written in the text style
+    fn text(color: &BoxColor, text: &str) -> String {
+        let mut out = String::new();
+        let styles = color.styles();
+        styles
+            .write_text(&mut out, |out| styles.write_message(out, text))
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn test_reopen_after_reset() {
+        let mut box_color = BoxColor::new();
+        box_color.text = LineColor::from_ansi(AnsiColorCode::Blue);
+        let wrapped_message = text(&box_color, "a \x1B[1mb\x1B[0m c");
+        let expected_message = "\x1B[34ma \x1B[1mb\x1B[0m\x1B[34m c\x1B[0m";
+        assert_eq!(wrapped_message, expected_message);
+    }
+
+    #[test]
+    fn test_reopen_after_other_resets() {
+        let mut box_color = BoxColor::new();
+        box_color.text = LineColor::from_ansi(AnsiColorCode::Blue);
+        assert_eq!(
+            text(&box_color, "a \x1B[1mb\x1B[m c"),
+            "\x1B[34ma \x1B[1mb\x1B[m\x1B[34m c\x1B[0m"
+        );
+        assert_eq!(
+            text(&box_color, "a \x1B[0;32mb"),
+            "\x1B[34ma \x1B[0;32m\x1B[34m\x1B[32mb\x1B[0m"
+        );
+    }
 
     #[test]
     fn test_color_8() {
@@ -90,7 +309,9 @@ mod tests {
 
         let message = "Arbitrary Text";
 
-        let wrapped_message = color.wrap_color(String::from(message));
+        let mut box_color = BoxColor::new();
+        box_color.border = color;
+        let wrapped_message = border(&box_color, message);
         let expected_message = format!("\x1B[38;5;{}m{}{}", color_code, message, RESET_CODE);
         assert_eq!(wrapped_message, expected_message);
     }
@@ -101,7 +322,9 @@ mod tests {
         let color_code = AnsiColorCode::BrightBlack;
         color.ansi = Some(color_code);
         let message = "Arbitrary text";
-        let wrapped_message = color.wrap_color(String::from(message));
+        let mut box_color = BoxColor::new();
+        box_color.border = color;
+        let wrapped_message = border(&box_color, message);
         let expected_message = format!("\x1B[{}m{}{}", "90", message, RESET_CODE);
         assert_eq!(wrapped_message, expected_message);
     }
@@ -117,11 +340,116 @@ mod tests {
         color.rgb = Some(rgb);
 
         let message = "Arbitrary text";
-        let wrapped_message = color.wrap_color(String::from(message));
+        let mut box_color = BoxColor::new();
+        box_color.border = color;
+        let wrapped_message = border(&box_color, message);
         let expected_message = format!(
             "\x1B[38;2;{};{};{}m{}{}",
             100, 101, 102, message, RESET_CODE
         );
         assert_eq!(wrapped_message, expected_message);
     }
+
+    #[test]
+    fn test_background() {
+        assert_eq!(
+            LineColor::from_ansi(AnsiColorCode::Red)
+                .background()
+                .unwrap(),
+            "41"
+        );
+        assert_eq!(
+            LineColor::from_ansi(AnsiColorCode::BrightWhite)
+                .background()
+                .unwrap(),
+            "107"
+        );
+        assert_eq!(LineColor::from_8(9).background().unwrap(), "48;5;9");
+        let rgb = RgbColor {
+            red: 1,
+            green: 2,
+            blue: 3,
+        };
+        assert_eq!(LineColor::from_rgb(rgb).background().unwrap(), "48;2;1;2;3");
+        assert_eq!(LineColor::new().background(), None);
+    }
+
+    #[test]
+    fn test_box_color_none() {
+        let color = BoxColor::new();
+        assert_eq!(border(&color, "│"), "│");
+        assert_eq!(text(&color, "Arbitrary text"), "Arbitrary text");
+    }
+
+    #[test]
+    fn test_box_color_border_only() {
+        let mut color = BoxColor::new();
+        color.border = LineColor::from_ansi(AnsiColorCode::Red);
+        assert_eq!(border(&color, "│"), format!("\x1B[31m│{}", RESET_CODE));
+        assert_eq!(text(&color, "Arbitrary text"), "Arbitrary text");
+    }
+
+    #[test]
+    fn test_box_color_text_and_background() {
+        let mut color = BoxColor::new();
+        color.text = LineColor::from_8(202);
+        color.background = LineColor::from_ansi(AnsiColorCode::Blue);
+        assert_eq!(border(&color, "│"), format!("\x1B[44m│{}", RESET_CODE));
+        assert_eq!(
+            text(&color, "Arbitrary text"),
+            format!("\x1B[38;5;202;44mArbitrary text{}", RESET_CODE)
+        );
+    }
+
+    #[test]
+    fn test_empty_message() {
+        let mut color = BoxColor::new();
+        color.text = LineColor::from_ansi(AnsiColorCode::Red);
+        let mut out = String::new();
+        color.styles().write_message(&mut out, "").unwrap();
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn test_attribute_codes() {
+        let attributes = [
+            Attribute::Bold,
+            Attribute::Dim,
+            Attribute::Italic,
+            Attribute::Underline,
+            Attribute::Blink,
+            Attribute::Reverse,
+            Attribute::Strikethrough,
+        ];
+        let codes = attributes.iter().map(attribute_code).collect::<Vec<u8>>();
+        assert_eq!(codes, vec![1, 2, 3, 4, 5, 7, 9]);
+    }
+
+    #[test]
+    fn test_attributes_without_color() {
+        let mut color = BoxColor::new();
+        color.text_attributes = vec![Attribute::Bold, Attribute::Underline];
+        assert_eq!(
+            text(&color, "Arbitrary text"),
+            format!("\x1B[1;4mArbitrary text{}", RESET_CODE)
+        );
+        assert_eq!(border(&color, "│"), "│");
+    }
+
+    #[test]
+    fn test_attributes_with_color() {
+        let mut color = BoxColor::new();
+        color.border = LineColor::from_ansi(AnsiColorCode::Red);
+        color.background = LineColor::from_8(236);
+        color.border_attributes = vec![Attribute::Dim];
+        color.text_attributes = vec![Attribute::Italic, Attribute::Strikethrough];
+        assert_eq!(
+            border(&color, "│"),
+            format!("\x1B[2;31;48;5;236m│{}", RESET_CODE)
+        );
+        assert_eq!(
+            text(&color, "Arbitrary text"),
+            format!("\x1B[3;9;48;5;236mArbitrary text{}", RESET_CODE)
+        );
+    }
 }
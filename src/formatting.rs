@@ -1,17 +1,102 @@
-/// Sets text alignment inside the line box to the left or right.
+use crate::terminal;
+
+/// Sets text alignment inside the line box.
+#[derive(Clone)]
 pub enum Alignment {
     Left,
     Right,
+    /// Center each line, any odd column of space goes to the right.
+    Center,
+    /// Spread the words of wrapped lines so they fill the width of the box.
+    /// The last line of each paragraph is left aligned.
+    Justify,
+}
+
+/// Sets where content shorter than the space it is given is placed.
+#[derive(Clone)]
+pub enum VerticalAlignment {
+    Top,
+    /// Center the content, any odd line of space goes to the bottom.
+    Middle,
+    Bottom,
+}
+
+/// Sets where text embedded in the top or bottom line of the box is placed.
+#[derive(Clone)]
+pub enum Position {
+    Left,
+    Center,
+    Right,
+}
+
+/// Sets how lines wider than the line box are broken.
+#[derive(Clone)]
+pub enum Wrapping {
+    /// Break lines at whitespace and other Unicode line break opportunities.
+    /// Words wider than the box are hyphenated, or split if they can't be.
+    Word,
+    /// Break lines at exactly the width of the box, even in the middle of a word.
+    Hard,
+}
+
+/// Sets what happens to text wider than the space it is given.
+#[derive(Clone)]
+pub enum Overflow {
+    /// Wrap the text onto as many lines as it takes.
+    Wrap,
+    /// Cut the end of the text off, leaving an ellipsis.
+    Truncate,
+    /// Cut the middle of the text out, leaving an ellipsis, like `/very/lo…/file.rs`.
+    TruncateMiddle,
+    /// Cut the start of the text off, leaving an ellipsis.
+    TruncateStart,
 }
 
+/// Sets what happens to content that is too tall for the height of the box.
+#[derive(Clone)]
+pub enum VerticalOverflow {
+    /// Drop the lines that don't fit.
+    Clip,
+    /// Drop the lines that don't fit and end with a count of them, like `… 12 more lines`.
+    Indicate,
+}
+
+/// Sets how a box is sized to the width of the terminal, in place of its max_width.
+#[derive(Clone)]
+pub enum TerminalFit {
+    /// Stretch the box across the full width of the terminal.
+    Fill,
+    /// Keep the box as narrow as its content, wrapping at the terminal width.
+    Shrink,
+}
+
+#[derive(Clone)]
 pub struct Formatting {
     pub padding: usize,
     pub alignment: Alignment,
+    pub vertical_alignment: VerticalAlignment,
     pub max_width: usize,
+    pub wrapping: Wrapping,
+    pub overflow: Overflow,
+    pub ellipsis: String,
+    pub title_position: Position,
     pub padding_left: Option<usize>,
     pub padding_right: Option<usize>,
     pub padding_top: Option<usize>,
     pub padding_bottom: Option<usize>,
+    pub margin: usize,
+    pub margin_left: Option<usize>,
+    pub margin_right: Option<usize>,
+    pub margin_top: Option<usize>,
+    pub margin_bottom: Option<usize>,
+    pub tab_width: usize,
+    pub terminal_fit: Option<TerminalFit>,
+    pub terminal_fd: i32,
+    pub width: Option<usize>,
+    pub min_width: usize,
+    pub height: Option<usize>,
+    pub min_height: usize,
+    pub vertical_overflow: VerticalOverflow,
 }
 
 impl Formatting {
@@ -19,11 +104,114 @@ impl Formatting {
         Formatting {
             padding: 2,
             alignment: Alignment::Left,
+            vertical_alignment: VerticalAlignment::Top,
             max_width: 80,
+            wrapping: Wrapping::Word,
+            overflow: Overflow::Wrap,
+            ellipsis: String::from("…"),
+            title_position: Position::Left,
             padding_left: None,
             padding_right: None,
             padding_top: None,
             padding_bottom: None,
+            margin: 0,
+            margin_left: None,
+            margin_right: None,
+            margin_top: None,
+            margin_bottom: None,
+            tab_width: 8,
+            terminal_fit: None,
+            terminal_fd: 1,
+            width: None,
+            min_width: 0,
+            height: None,
+            min_height: 0,
+            vertical_overflow: VerticalOverflow::Indicate,
         }
     }
+
+    /// The widest the box may be, and the narrowest it must be, in columns.
+    ///
+    /// The max_width and the terminal width include the margins, which are left
+    /// out of the space for the box. An exact width or min_width is the box's own.
+    pub fn widths(&self) -> (usize, usize) {
+        match self.terminal_fit {
+            Some(_) => self.widths_in(terminal::width_of(self.terminal_fd)),
+            None => self.widths_in(0),
+        }
+    }
+
+    /// Like `widths`, with a terminal `terminal_width` columns wide
+    pub fn widths_in(&self, terminal_width: usize) -> (usize, usize) {
+        if let Some(width) = self.width {
+            return (width, width);
+        }
+        let (max_width, min_width) = match self.terminal_fit {
+            None => (self.max_width, 0),
+            Some(TerminalFit::Shrink) => (terminal_width, 0),
+            Some(TerminalFit::Fill) => (terminal_width, terminal_width),
+        };
+        let margin = self.left_margin() + self.right_margin();
+        (
+            max_width.saturating_sub(margin),
+            min_width.saturating_sub(margin).max(self.min_width),
+        )
+    }
+
+    /// The most lines the box may take, if it is limited, and the fewest it must take
+    pub fn heights(&self) -> (Option<usize>, usize) {
+        match self.height {
+            Some(height) => (Some(height), height),
+            None => (None, self.min_height),
+        }
+    }
+
+    /// Rows above and below the content when it is given `extra` more rows than it takes
+    pub fn vertical_space(&self, extra: usize) -> (usize, usize) {
+        match self.vertical_alignment {
+            VerticalAlignment::Top => (0, extra),
+            VerticalAlignment::Middle => (extra / 2, extra - extra / 2),
+            VerticalAlignment::Bottom => (extra, 0),
+        }
+    }
+
+    /// Padding between the left line and the content
+    pub fn left_padding(&self) -> usize {
+        self.padding_left.unwrap_or(self.padding)
+    }
+
+    /// Padding between the content and the right line
+    pub fn right_padding(&self) -> usize {
+        self.padding_right.unwrap_or(self.padding)
+    }
+
+    /// Rows of padding between the top line and the content
+    pub fn top_padding(&self) -> usize {
+        self.padding_top.unwrap_or(self.padding / 2)
+    }
+
+    /// Rows of padding between the content and the bottom line
+    pub fn bottom_padding(&self) -> usize {
+        self.padding_bottom.unwrap_or(self.padding / 2)
+    }
+
+    /// Columns of margin left of the box
+    pub fn left_margin(&self) -> usize {
+        self.margin_left.unwrap_or(self.margin)
+    }
+
+    /// Columns of margin right of the box
+    pub fn right_margin(&self) -> usize {
+        self.margin_right.unwrap_or(self.margin)
+    }
+
+    /// Rows of margin above the box
+    pub fn top_margin(&self) -> usize {
+        self.margin_top.unwrap_or(self.margin / 2)
+    }
+
+    /// Rows of margin below the box
+    pub fn bottom_margin(&self) -> usize {
+        self.margin_bottom.unwrap_or(self.margin / 2)
+    }
 }
@@ -0,0 +1,878 @@
+/// Boxes drawn as the cells of one grid, sharing their borders
+use std::error::Error;
+use std::fmt;
+
+use crate::color::{BoxColor, LineColor, Styles};
+use crate::helper;
+use crate::lines::line_type::LineType;
+use crate::lines::{Lines, LinesError};
+use crate::{Alignment, AnsiColorCode, BoxBuilder, RgbColor};
+
+/// The most places, counting empty ones, a grid may have
+const MAX_PLACES: usize = 1 << 16;
+
+/// The rows and columns a cell covers
+#[derive(Clone, Copy)]
+struct Area {
+    row: usize,
+    column: usize,
+    rows: usize,
+    columns: usize,
+}
+
+/// Grid builder struct that represents a panel of cells sharing their borders.
+///
+/// Each cell is drawn with the content, alignment, padding and wrapping of a
+/// [BoxBuilder](struct.BoxBuilder.html), and everything else comes from the grid.
+/// Cells may span several rows and columns, and any place not covered by a
+/// cell is drawn as an empty cell.
+/// ```
+/// use bauxite::{BoxBuilder, GridBuilder};
+///
+/// let cell = |message| BoxBuilder::from(message).padding(0);
+/// let grid = GridBuilder::new()
+///     .cell_span(0, 0, 2, 1, cell("left"))?
+///     .cell(0, 1, cell("top"))?
+///     .cell(1, 1, cell("bottom"))?;
+/// assert_eq!(
+///     grid.to_string(),
+///     "┌────┬──────┐\n\
+///      │left│top   │\n\
+///      │    ├──────┤\n\
+///      │    │bottom│\n\
+///      └────┴──────┘"
+/// );
+/// # Ok::<(), bauxite::GridError>(())
+/// ```
+pub struct GridBuilder {
+    cells: Vec<(Area, BoxBuilder)>,
+    rows: usize,
+    columns: usize,
+    max_width: usize,
+    lines: Lines,
+    color: BoxColor,
+}
+
+impl GridBuilder {
+    /// Create a new grid with no cells
+    pub fn new() -> GridBuilder {
+        GridBuilder {
+            cells: Vec::new(),
+            rows: 0,
+            columns: 0,
+            max_width: 80,
+            lines: Lines::new(),
+            color: BoxColor::new(),
+        }
+    }
+
+    /// Put a cell at a row and column, counting from zero.
+    ///
+    /// Returns an error if the grid would have too many places to draw.
+    pub fn cell(self, row: usize, column: usize, content: BoxBuilder) -> Result<Self, GridError> {
+        self.cell_span(row, column, 1, 1, content)
+    }
+
+    /// Put a cell at a row and column that spans a number of rows and columns.
+    /// Cells overlapping a cell that was put in the grid before them aren't drawn.
+    ///
+    /// Returns an error if the grid would have too many places to draw.
+    pub fn cell_span(
+        mut self,
+        row: usize,
+        column: usize,
+        rows: usize,
+        columns: usize,
+        content: BoxBuilder,
+    ) -> Result<Self, GridError> {
+        let area = Area {
+            row,
+            column,
+            rows: rows.max(1),
+            columns: columns.max(1),
+        };
+        let grid_rows = row
+            .checked_add(area.rows)
+            .ok_or(GridError::TooLarge)?
+            .max(self.rows);
+        let grid_columns = column
+            .checked_add(area.columns)
+            .ok_or(GridError::TooLarge)?
+            .max(self.columns);
+        match grid_rows.checked_mul(grid_columns) {
+            Some(places) if places <= MAX_PLACES => {}
+            _ => return Err(GridError::TooLarge),
+        }
+        self.rows = grid_rows;
+        self.columns = grid_columns;
+        self.cells.push((area, content));
+        Ok(self)
+    }
+
+    /// Set the widest the grid may be. Columns are sized to their content,
+    /// and the widest columns are narrowed until the grid fits.
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    /// Set the type of lines to draw using [LineType](enum.LineType.html)
+    pub fn line_type(mut self, line_type: LineType) -> Self {
+        self.lines = Lines::from(line_type);
+        self
+    }
+
+    /// Set the lines to draw the grid with using [Lines](struct.Lines.html).
+    ///
+    /// Returns an error if the lines wouldn't make a rectangular grid.
+    pub fn lines(mut self, lines: Lines) -> Result<Self, LinesError> {
+        lines.validate()?;
+        self.lines = lines;
+        Ok(self)
+    }
+
+    /// Sets 8 bit color code of the lines, see
+    /// [BoxBuilder::color_8](struct.BoxBuilder.html#method.color_8).
+    pub fn color_8(mut self, color: u8) -> Self {
+        self.color.border = LineColor::from_8(color);
+        self
+    }
+
+    /// Basic RGB colors of the lines.
+    pub fn color_rgb(mut self, red: u8, green: u8, blue: u8) -> Self {
+        self.color.border = LineColor::from_rgb(RgbColor { red, green, blue });
+        self
+    }
+
+    /// Simplest ANSI color codes of the lines.
+    pub fn color(mut self, code: AnsiColorCode) -> Self {
+        self.color.border = LineColor::from_ansi(code);
+        self
+    }
+
+    /// Sets 8 bit color code of the cell text.
+    pub fn text_color_8(mut self, color: u8) -> Self {
+        self.color.text = LineColor::from_8(color);
+        self
+    }
+
+    /// Basic RGB colors of the cell text.
+    pub fn text_color_rgb(mut self, red: u8, green: u8, blue: u8) -> Self {
+        self.color.text = LineColor::from_rgb(RgbColor { red, green, blue });
+        self
+    }
+
+    /// Simplest ANSI color codes of the cell text.
+    pub fn text_color(mut self, code: AnsiColorCode) -> Self {
+        self.color.text = LineColor::from_ansi(code);
+        self
+    }
+
+    /// Grid to string.
+    /// Returns the full grid with every cell in place
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        let mut grid = String::new();
+        self.write_grid(&mut grid)
+            .expect("a String can always be written to");
+        grid
+    }
+
+    /// Write the grid into `out` a piece at a time, without building it as a String first
+    fn write_grid<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let empty = BoxBuilder::from("").padding(0);
+        let (slots, cells) = self.place(&empty);
+        if cells.is_empty() {
+            return Ok(());
+        }
+        let layout = Layout::new(self, slots, cells);
+
+        let styles = self.color.styles();
+        for y in 0..layout.heights.len() {
+            layout.write_boundary(out, &styles, y)?;
+            out.write_char('\n')?;
+            for line in 1..=layout.heights[y] {
+                layout.write_content(out, &styles, y, layout.boundaries[y] + line)?;
+                out.write_char('\n')?;
+            }
+        }
+        layout.write_boundary(out, &styles, layout.heights.len())
+    }
+
+    /// Which cell covers each place in the grid, filling gaps with empty cells
+    fn place<'a>(
+        &'a self,
+        empty: &'a BoxBuilder,
+    ) -> (Vec<Vec<usize>>, Vec<(Area, &'a BoxBuilder)>) {
+        let (rows, columns) = (self.rows, self.columns);
+        let mut slots = vec![vec![None; columns]; rows];
+        let mut cells = Vec::new();
+        for (area, content) in &self.cells {
+            let covered = (area.row..area.row + area.rows)
+                .flat_map(|row| (area.column..area.column + area.columns).map(move |c| (row, c)))
+                .collect::<Vec<(usize, usize)>>();
+            if covered
+                .iter()
+                .any(|(row, column)| slots[*row][*column].is_some())
+            {
+                continue;
+            }
+            for (row, column) in covered {
+                slots[row][column] = Some(cells.len());
+            }
+            cells.push((*area, content));
+        }
+
+        let mut filled = vec![vec![0; columns]; rows];
+        for row in 0..rows {
+            for column in 0..columns {
+                filled[row][column] = slots[row][column].unwrap_or_else(|| {
+                    let area = Area {
+                        row,
+                        column,
+                        rows: 1,
+                        columns: 1,
+                    };
+                    cells.push((area, empty));
+                    cells.len() - 1
+                });
+            }
+        }
+        (filled, cells)
+    }
+}
+
+/// Implement fmt for GridBuilder so we can use pass a GridBuilder to `println!` for printing
+impl fmt::Display for GridBuilder {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.write_grid(formatter)
+    }
+}
+
+impl Default for GridBuilder {
+    fn default() -> Self {
+        GridBuilder::new()
+    }
+}
+
+/// Reasons a cell can't be put in a [GridBuilder](struct.GridBuilder.html).
+#[derive(Debug, PartialEq)]
+pub enum GridError {
+    /// The cell's position or span would make the grid too large to draw.
+    TooLarge,
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::TooLarge => write!(
+                formatter,
+                "a grid can't have more than {} places",
+                MAX_PLACES
+            ),
+        }
+    }
+}
+impl Error for GridError {}
+
+/// The sizes of every row and column of a grid, and the lines of every cell
+struct Layout<'a> {
+    lines: &'a Lines,
+    slots: Vec<Vec<usize>>,
+    cells: Vec<(Area, &'a BoxBuilder)>,
+    widths: Vec<usize>,
+    heights: Vec<usize>,
+    /// Line each horizontal line of the grid is drawn on
+    boundaries: Vec<usize>,
+    blocks: Vec<Vec<String>>,
+}
+
+impl<'a> Layout<'a> {
+    fn new(
+        grid: &'a GridBuilder,
+        slots: Vec<Vec<usize>>,
+        cells: Vec<(Area, &'a BoxBuilder)>,
+    ) -> Layout<'a> {
+        let mut layout = Layout {
+            lines: &grid.lines,
+            widths: vec![0; slots[0].len()],
+            heights: vec![0; slots.len()],
+            boundaries: Vec::new(),
+            blocks: Vec::new(),
+            slots,
+            cells,
+        };
+        layout.size_columns(grid.max_width);
+        let contents = layout
+            .cells
+            .iter()
+            .map(|(area, content)| {
+                let width = layout.span_width(area);
+                match content.justified_paragraphs(width, 0) {
+                    Some(paragraphs) => {
+                        let padding =
+                            content.format.left_padding() + content.format.right_padding();
+                        helper::join_paragraphs(
+                            &paragraphs,
+                            &Alignment::Justify,
+                            width.saturating_sub(padding),
+                        )
+                    }
+                    None => content.normalize_content(width, 0),
+                }
+            })
+            .collect::<Vec<String>>();
+        layout.size_rows(&contents);
+        layout.blocks = layout
+            .cells
+            .iter()
+            .zip(&contents)
+            .map(|((area, content), message)| layout.gen_block(area, content, message))
+            .collect();
+        layout
+    }
+
+    /// Size each column to its widest cell, then narrow the widest columns
+    /// one column at a time until the grid fits in `max_width`
+    fn size_columns(&mut self, max_width: usize) {
+        let separators = self.vertical_width() * (self.widths.len() - 1);
+        let available = max_width.saturating_sub(self.lines.edge_width() + separators);
+        let natural = self
+            .cells
+            .iter()
+            .map(|(area, content)| {
+                let format = &content.format;
+                let message = content.normalize_content(available, 0);
+                let width = helper::max_line_length(&message);
+                let padding = format.left_padding() + format.right_padding();
+                (
+                    area,
+                    width + padding,
+                    padding + if width > 0 { 1 } else { 0 },
+                )
+            })
+            .collect::<Vec<(&Area, usize, usize)>>();
+
+        let mut floors = vec![1; self.widths.len()];
+        for (area, width, floor) in &natural {
+            if area.columns == 1 {
+                self.widths[area.column] = self.widths[area.column].max(*width);
+                floors[area.column] = floors[area.column].max(*floor);
+            }
+        }
+
+        let mut spanning = natural
+            .iter()
+            .filter(|(area, _, _)| area.columns > 1)
+            .collect::<Vec<&(&Area, usize, usize)>>();
+        spanning.sort_by_key(|(area, _, _)| area.columns);
+        for (area, width, _) in spanning {
+            let extra = width.saturating_sub(self.span_width(area));
+            grow(
+                &mut self.widths[area.column..area.column + area.columns],
+                extra,
+            );
+        }
+
+        while self.widths.iter().sum::<usize>() > available {
+            let widest = self
+                .widths
+                .iter()
+                .enumerate()
+                .filter(|(index, width)| **width > floors[*index])
+                .max_by_key(|(index, width)| (**width, usize::MAX - index))
+                .map(|(index, _)| index);
+            match widest {
+                Some(index) => self.widths[index] -= 1,
+                None => break,
+            }
+        }
+    }
+
+    /// Size each row to its tallest cell
+    fn size_rows(&mut self, contents: &[String]) {
+        let natural = self
+            .cells
+            .iter()
+            .zip(contents)
+            .map(|((area, content), message)| {
+                let format = &content.format;
+                let height =
+                    format.top_padding() + message.lines().count() + format.bottom_padding();
+                (*area, height)
+            })
+            .collect::<Vec<(Area, usize)>>();
+
+        for height in self.heights.iter_mut() {
+            *height = 1;
+        }
+        for (area, height) in &natural {
+            if area.rows == 1 {
+                self.heights[area.row] = self.heights[area.row].max(*height);
+            }
+        }
+        let mut spanning = natural
+            .iter()
+            .filter(|(area, _)| area.rows > 1)
+            .collect::<Vec<&(Area, usize)>>();
+        spanning.sort_by_key(|(area, _)| area.rows);
+        for (area, height) in spanning {
+            let extra = height.saturating_sub(self.span_height(area));
+            grow(&mut self.heights[area.row..area.row + area.rows], extra);
+        }
+
+        self.boundaries = vec![0];
+        for height in &self.heights {
+            let last = self.boundaries[self.boundaries.len() - 1];
+            self.boundaries.push(last + height + 1);
+        }
+    }
+
+    /// Columns between the lines either side of a cell
+    fn span_width(&self, area: &Area) -> usize {
+        self.widths[area.column..area.column + area.columns]
+            .iter()
+            .sum::<usize>()
+            + self.vertical_width() * (area.columns - 1)
+    }
+
+    /// Lines between the lines above and below a cell
+    fn span_height(&self, area: &Area) -> usize {
+        self.heights[area.row..area.row + area.rows]
+            .iter()
+            .sum::<usize>()
+            + area.rows
+            - 1
+    }
+
+    fn vertical_width(&self) -> usize {
+        helper::display_width(&self.lines.vertical)
+    }
+
+    /// Helper function to build every line of a cell, exactly as wide and tall as it is drawn
+    fn gen_block(&self, area: &Area, content: &BoxBuilder, message: &str) -> Vec<String> {
+        let format = &content.format;
+        let width = self.span_width(area);
+        let text_width = width.saturating_sub(format.left_padding() + format.right_padding());
+        let blank = helper::gen_whitespace(width);
+
+        let mut block = vec![blank.clone(); format.top_padding()];
+        for line in message.lines() {
+            let line = helper::truncate(line, text_width);
+            let line_width = helper::display_width(&line);
+            let row = format!(
+                "{}{}{}",
+                helper::gen_whitespace(content.left_space(format, line_width, text_width)),
+                line,
+                helper::gen_whitespace(content.right_space(format, line_width, text_width))
+            );
+            block.push(fit(&row, width));
+        }
+        block.resize(self.span_height(area), blank);
+        block
+    }
+
+    /// Whether a vertical line is drawn on the left of a column in a row
+    fn has_vertical(&self, row: usize, column: usize) -> bool {
+        column == 0
+            || column == self.widths.len()
+            || self.slots[row][column - 1] != self.slots[row][column]
+    }
+
+    /// Whether a horizontal line is drawn above a row in a column
+    fn has_horizontal(&self, row: usize, column: usize) -> bool {
+        row == 0
+            || row == self.heights.len()
+            || self.slots[row - 1][column] != self.slots[row][column]
+    }
+
+    /// Helper function to write the line of the grid above row `y`, or below the last row
+    fn write_boundary<W: fmt::Write>(&self, out: &mut W, styles: &Styles, y: usize) -> fmt::Result {
+        let line = self.boundaries[y];
+        let edge = if y == 0 {
+            &self.lines.top
+        } else if y == self.heights.len() {
+            &self.lines.bottom
+        } else {
+            &self.lines.horizontal
+        };
+
+        for column in 0..=self.widths.len() {
+            if let Some(junction) = self.junction(y, column, edge) {
+                write_piece(out, styles, &junction)?;
+            }
+            if column == self.widths.len() {
+                break;
+            }
+            if self.has_horizontal(y, column) {
+                let width = self.widths[column];
+                if width > 0 {
+                    styles.write_border(out, |out| helper::write_repeated(out, edge, width))?;
+                }
+            } else {
+                self.write_cell_line(out, styles, y, column, line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Helper function to write a line of the grid through the cells of row `y`
+    fn write_content<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        y: usize,
+        line: usize,
+    ) -> fmt::Result {
+        for column in 0..self.widths.len() {
+            if self.has_vertical(y, column) {
+                let piece = if column == 0 {
+                    &self.lines.left
+                } else {
+                    &self.lines.vertical
+                };
+                write_piece(out, styles, piece)?;
+            }
+            self.write_cell_line(out, styles, y, column, line)?;
+        }
+        write_piece(out, styles, &self.lines.right)
+    }
+
+    /// Write the line of the cell covering a place, drawn where the cell starts
+    fn write_cell_line<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        row: usize,
+        column: usize,
+        line: usize,
+    ) -> fmt::Result {
+        let index = self.slots[row][column];
+        let (area, _) = &self.cells[index];
+        if area.column != column {
+            return Ok(());
+        }
+        let text = &self.blocks[index][line - self.boundaries[area.row] - 1];
+        if text.is_empty() {
+            return Ok(());
+        }
+        styles.write_text(out, |out| styles.write_message(out, text))
+    }
+
+    /// The piece drawn where the lines left of `column` meet the line above row `y`.
+    /// Nothing is drawn inside a cell spanning both rows and columns.
+    fn junction(&self, y: usize, column: usize, edge: &str) -> Option<String> {
+        let up = y > 0 && self.has_vertical(y - 1, column);
+        let down = y < self.heights.len() && self.has_vertical(y, column);
+        let left = column > 0 && self.has_horizontal(y, column - 1);
+        let right = column < self.widths.len() && self.has_horizontal(y, column);
+
+        let lines = self.lines;
+        let piece = match (up, down, left, right) {
+            (true, true, true, true) => &lines.cross,
+            (false, true, true, true) => &lines.top_tee,
+            (true, false, true, true) => &lines.bottom_tee,
+            (true, true, false, true) => &lines.left_tee,
+            (true, true, true, false) => &lines.right_tee,
+            (false, true, false, true) => &lines.top_left,
+            (false, true, true, false) => &lines.top_right,
+            (true, false, false, true) => &lines.bottom_left,
+            (true, false, true, false) => &lines.bottom_right,
+            (true, true, false, false) if column == 0 => &lines.left,
+            (true, true, false, false) if column == self.widths.len() => &lines.right,
+            (true, true, false, false) => &lines.vertical,
+            (false, false, true, true) => {
+                return Some(helper::repeat_to_width(edge, self.vertical_width()));
+            }
+            _ => return None,
+        };
+        Some(piece.clone())
+    }
+}
+
+/// Write one piece of the lines in the border colors, if it takes up any room
+fn write_piece<W: fmt::Write>(out: &mut W, styles: &Styles, piece: &str) -> fmt::Result {
+    if piece.is_empty() {
+        return Ok(());
+    }
+    styles.write_border(out, |out| out.write_str(piece))
+}
+
+/// Spread `extra` over the sizes, giving any remainder to the first ones
+fn grow(sizes: &mut [usize], extra: usize) {
+    let count = sizes.len();
+    for (index, size) in sizes.iter_mut().enumerate() {
+        *size += extra / count + if index < extra % count { 1 } else { 0 };
+    }
+}
+
+/// Cut or pad a line to exactly `width` columns
+fn fit(line: &str, width: usize) -> String {
+    let line = helper::truncate(line, width);
+    let fill = width - helper::display_width(&line);
+    line + &helper::gen_whitespace(fill)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(message: &str) -> BoxBuilder {
+        BoxBuilder::from(message).padding(0)
+    }
+
+    #[test]
+    fn test_simple_grid() {
+        let expected = "┌─┬─┐\n\
+                        │a│b│\n\
+                        ├─┼─┤\n\
+                        │c│d│\n\
+                        └─┴─┘";
+        let grid = GridBuilder::new()
+            .cell(0, 0, cell("a"))
+            .unwrap()
+            .cell(0, 1, cell("b"))
+            .unwrap()
+            .cell(1, 0, cell("c"))
+            .unwrap()
+            .cell(1, 1, cell("d"))
+            .unwrap();
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_double_grid() {
+        let expected = "╔═╦═╗\n\
+                        ║a║b║\n\
+                        ╠═╬═╣\n\
+                        ║c║d║\n\
+                        ╚═╩═╝";
+        let grid = GridBuilder::new()
+            .line_type(LineType::Double)
+            .cell(0, 0, cell("a"))
+            .unwrap()
+            .cell(0, 1, cell("b"))
+            .unwrap()
+            .cell(1, 0, cell("c"))
+            .unwrap()
+            .cell(1, 1, cell("d"))
+            .unwrap();
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_column_span() {
+        let expected = "┌──────┐\n\
+                        │header│\n\
+                        ├───┬──┤\n\
+                        │a  │b │\n\
+                        └───┴──┘";
+        let grid = GridBuilder::new()
+            .cell_span(0, 0, 1, 2, cell("header"))
+            .unwrap()
+            .cell(1, 0, cell("a"))
+            .unwrap()
+            .cell(1, 1, cell("b"))
+            .unwrap();
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_row_span() {
+        let expected = "┌─┬─┐\n\
+                        │L│a│\n\
+                        │ ├─┤\n\
+                        │ │b│\n\
+                        └─┴─┘";
+        let grid = GridBuilder::new()
+            .cell_span(0, 0, 2, 1, cell("L"))
+            .unwrap()
+            .cell(0, 1, cell("a"))
+            .unwrap()
+            .cell(1, 1, cell("b"))
+            .unwrap();
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_row_and_column_span() {
+        let expected = "┌───┬─┐\n\
+                        │big│c│\n\
+                        │   ├─┤\n\
+                        │   │d│\n\
+                        ├─┬─┼─┤\n\
+                        │e│f│g│\n\
+                        └─┴─┴─┘";
+        let grid = GridBuilder::new()
+            .cell_span(0, 0, 2, 2, cell("big"))
+            .unwrap()
+            .cell(0, 2, cell("c"))
+            .unwrap()
+            .cell(1, 2, cell("d"))
+            .unwrap()
+            .cell(2, 0, cell("e"))
+            .unwrap()
+            .cell(2, 1, cell("f"))
+            .unwrap()
+            .cell(2, 2, cell("g"))
+            .unwrap();
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_empty_places() {
+        let expected = "┌─┬─┐\n\
+                        │a│ │\n\
+                        ├─┼─┤\n\
+                        │ │b│\n\
+                        └─┴─┘";
+        let grid = GridBuilder::new()
+            .cell(0, 0, cell("a"))
+            .unwrap()
+            .cell(1, 1, cell("b"))
+            .unwrap();
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_overlapping_cell_ignored() {
+        let expected = "┌─┐\n\
+                        │a│\n\
+                        └─┘";
+        let grid = GridBuilder::new()
+            .cell(0, 0, cell("a"))
+            .unwrap()
+            .cell(0, 0, cell("b"))
+            .unwrap();
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_cell_alignment_and_padding() {
+        let expected = "┌────────┬─────┐\n\
+                        │ longer │     │\n\
+                        ├────────┤  c  │\n\
+                        │      r │     │\n\
+                        └────────┴─────┘";
+        let grid = GridBuilder::new()
+            .cell(0, 0, BoxBuilder::from("longer").padding(1))
+            .unwrap()
+            .cell(
+                1,
+                0,
+                BoxBuilder::from("r").padding(1).alignment(Alignment::Right),
+            )
+            .unwrap()
+            .cell_span(
+                0,
+                1,
+                2,
+                1,
+                BoxBuilder::from("c")
+                    .padding(2)
+                    .padding_top(1)
+                    .alignment(Alignment::Center),
+            )
+            .unwrap();
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_justify_to_column_width() {
+        let expected = "┌────────────┐\n\
+                        │wide header!│\n\
+                        ├────────────┤\n\
+                        │aa  bb cc dd│\n\
+                        │ee          │\n\
+                        └────────────┘";
+        let grid = GridBuilder::new()
+            .max_width(14)
+            .cell(0, 0, cell("wide header!"))
+            .unwrap()
+            .cell(1, 0, cell("aa bb cc dd ee").alignment(Alignment::Justify))
+            .unwrap();
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_fits_max_width() {
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+        let grid = GridBuilder::new()
+            .cell(0, 0, cell("key"))
+            .unwrap()
+            .cell(0, 1, BoxBuilder::from(message).padding(1))
+            .unwrap()
+            .cell_span(1, 0, 1, 2, cell(message))
+            .unwrap()
+            .max_width(24);
+        let rendered = grid.to_string();
+        let width = helper::display_width(rendered.lines().next().unwrap());
+        assert!(width <= 24);
+        for line in rendered.lines() {
+            assert_eq!(helper::display_width(line), width);
+        }
+        assert!(rendered.starts_with("┌───┬──"));
+    }
+
+    #[test]
+    fn test_colors() {
+        let grid = GridBuilder::new()
+            .cell(0, 0, cell("a"))
+            .unwrap()
+            .color(AnsiColorCode::Red);
+        let expected = "\x1B[31m┌\x1B[0m\x1B[31m─\x1B[0m\x1B[31m┐\x1B[0m\n\
+                        \x1B[31m│\x1B[0ma\x1B[31m│\x1B[0m\n\
+                        \x1B[31m└\x1B[0m\x1B[31m─\x1B[0m\x1B[31m┘\x1B[0m";
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn test_cell_too_large() {
+        let grid = GridBuilder::new().cell(usize::MAX, 0, cell("a"));
+        assert_eq!(grid.err(), Some(GridError::TooLarge));
+        let grid = GridBuilder::new().cell_span(0, 0, 1, usize::MAX, cell("a"));
+        assert_eq!(grid.err(), Some(GridError::TooLarge));
+        let grid = GridBuilder::new().cell_span(0, 0, 1 << 9, 1 << 9, cell("a"));
+        assert_eq!(grid.err(), Some(GridError::TooLarge));
+    }
+
+    #[test]
+    fn test_cells_too_far_apart() {
+        let grid = GridBuilder::new()
+            .cell(1000, 0, cell("a"))
+            .unwrap()
+            .cell(0, 1000, cell("b"));
+        assert_eq!(grid.err(), Some(GridError::TooLarge));
+    }
+
+    #[test]
+    fn test_grid_error_display() {
+        assert_eq!(
+            GridError::TooLarge.to_string(),
+            "a grid can't have more than 65536 places"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = GridBuilder::new()
+            .cell(0, 0, cell("\x1B[1ma\x1B[0m b"))
+            .unwrap()
+            .cell(1, 1, cell("c"))
+            .unwrap()
+            .color(AnsiColorCode::Red)
+            .text_color(AnsiColorCode::Blue);
+        assert_eq!(format!("{}", grid), grid.to_string());
+        assert!(grid
+            .to_string()
+            .starts_with("\x1B[31m┌\x1B[0m\x1B[31m───\x1B[0m"));
+        assert!(grid
+            .to_string()
+            .contains("\x1B[34m\x1B[1ma\x1B[0m\x1B[34m b\x1B[0m"));
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        assert_eq!(GridBuilder::new().to_string(), "");
+    }
+}
@@ -1,54 +1,657 @@
 /// Helper functions to facilitate line box formatting
+use std::borrow::Cow;
 use std::cmp::max;
+use std::fmt;
+use std::io;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::ansi::{self, Segment};
+use crate::formatting::{Alignment, Formatting, Overflow, Wrapping};
+use crate::wrap;
 
 /// Set a uniform line length. Line length is no more than max_width.
-pub fn normalize_lines(message: &String, max_width: usize, padding: usize) -> String {
-    let mut normalized_message = String::new();
-    let mut message_lines = message.lines();
-    let mut current = message_lines.next();
+///
+/// Justified lines are spread to the widest line of the message.
+pub fn normalize_lines(message: &str, format: &Formatting, border: usize) -> String {
+    let paragraphs = wrap_paragraphs(message, format, border);
+    let width = paragraphs
+        .iter()
+        .flatten()
+        .map(|line| display_width(line))
+        .max()
+        .unwrap_or(0);
+    join_paragraphs(&paragraphs, &format.alignment, width)
+}
 
-    while let Some(line) = current {
-        if line.len() > max_width {
-            let (line1, line2) = line.split_at(max_width - padding - 2);
-            normalized_message += line1;
-            normalized_message += "\n";
-            current = Some(line2);
-        } else {
-            normalized_message += line;
+/// Wrap each line of the message to fit within max_width, less the padding and `border`.
+/// Returns the wrapped lines of each line of the message, as a paragraph.
+pub fn wrap_paragraphs(message: &str, format: &Formatting, border: usize) -> Vec<Vec<String>> {
+    let padding = format.left_padding() + format.right_padding();
+    let mut paragraphs = message
+        .lines()
+        .map(|line| expand_tabs(line, format.tab_width))
+        .map(|line| match (&format.overflow, &format.wrapping) {
+            (Overflow::Wrap, Wrapping::Word) => {
+                wrap::wrap_line(&line, format.max_width.saturating_sub(padding + border))
+            }
+            (Overflow::Wrap, Wrapping::Hard) => {
+                hard_wrap_line(&line, format.max_width, padding + border)
+            }
+            (overflow, _) => vec![truncate_with(
+                &line,
+                format.max_width.saturating_sub(padding + border),
+                overflow,
+                &format.ellipsis,
+            )],
+        })
+        .collect::<Vec<Vec<String>>>();
+    ansi::carry_styles(paragraphs.iter_mut().flatten());
+    paragraphs
+}
+
+/// Join wrapped paragraphs into one line each.
+///
+/// Justifying needs to know which wrapped lines end a paragraph of the original
+/// message: every other line is spread out to `width` columns.
+pub fn join_paragraphs(paragraphs: &[Vec<String>], alignment: &Alignment, width: usize) -> String {
+    let mut normalized_message = String::new();
+    for paragraph in paragraphs {
+        for (index, line) in paragraph.iter().enumerate() {
+            match alignment {
+                Alignment::Justify if index + 1 < paragraph.len() => {
+                    normalized_message += &justify_line(line, width)
+                }
+                _ => normalized_message += line,
+            }
             normalized_message += "\n";
-            current = message_lines.next();
         }
     }
+    normalized_message
+}
+
+/// Replace each tab with spaces up to the next tab stop.
+///
+/// Tab stops are every `tab_width` columns from the start of the line.
+/// A tab width of zero removes tabs.
+pub fn expand_tabs(line: &str, tab_width: usize) -> Cow<'_, str> {
+    if !line.contains('\t') {
+        return Cow::Borrowed(line);
+    }
+    let mut expanded = String::new();
+    let mut columns = 0;
+    for (_, segment) in ansi::segments(line) {
+        match segment {
+            Segment::Grapheme("\t") if tab_width > 0 => {
+                let spaces = tab_width - columns % tab_width;
+                expanded += &gen_whitespace(spaces);
+                columns += spaces;
+            }
+            Segment::Grapheme("\t") => {}
+            Segment::Grapheme(grapheme) => {
+                expanded += grapheme;
+                columns += display_width(grapheme);
+            }
+            Segment::Escape(escape) => expanded += escape,
+        }
+    }
+    Cow::Owned(expanded)
+}
 
-    // Bauxite doesn't handle the tab character very well so
-    // replace all tab characters with a single space.
-    normalized_message.replace("\t", " ")
+/// Split a line wider than max_width at a fixed column.
+///
+/// The column is `max_width - padding`, where padding includes the lines, or
+/// zero when the padding doesn't fit in max_width, in which case every
+/// grapheme gets a line.
+fn hard_wrap_line(line: &str, max_width: usize, padding: usize) -> Vec<String> {
+    let mut wrapped = Vec::new();
+    let mut current = line;
+    while display_width(current) > max_width {
+        let (head, tail) = split_at_width(current, max_width.saturating_sub(padding));
+        wrapped.push(String::from(head));
+        current = tail;
+    }
+    if !current.is_empty() || wrapped.is_empty() {
+        wrapped.push(String::from(current));
+    }
+    wrapped
+}
+
+/// Spread the words of a line apart so that it is exactly `width` columns wide.
+///
+/// Extra space goes to the leftmost gaps first. Lines with a single word, or
+/// lines that are already wide enough, are returned unchanged.
+pub fn justify_line(line: &str, width: usize) -> String {
+    let words = line.split_whitespace().collect::<Vec<&str>>();
+    let words_width = words.iter().map(|word| display_width(word)).sum::<usize>();
+    if words.len() < 2 || words_width + words.len() - 1 > width {
+        return String::from(line);
+    }
+
+    let gaps = words.len() - 1;
+    let spaces = width - words_width;
+    let mut justified = String::from(words[0]);
+    for (index, word) in words.iter().enumerate().skip(1) {
+        let gap = spaces / gaps + if index <= spaces % gaps { 1 } else { 0 };
+        justified += &gen_whitespace(gap);
+        justified += word;
+    }
+    justified
+}
+
+/// Number of terminal columns a string occupies when printed.
+///
+/// East Asian wide characters take two columns, combining marks and
+/// zero width joiners take none. ANSI escape sequences, such as the colors
+/// of a box drawn inside another box, take none either.
+pub fn display_width(text: &str) -> usize {
+    if text.contains('\x1B') {
+        ansi::strip_escapes(text).width()
+    } else {
+        text.width()
+    }
+}
+
+/// Split a line so that the first part is at most `width` columns wide.
+///
+/// Lines are only split between grapheme clusters, so a character is never
+/// separated from its combining marks, and never inside an escape sequence.
+/// At least one grapheme is always moved to the first part so that callers
+/// splitting in a loop always make progress.
+pub fn split_at_width(line: &str, width: usize) -> (&str, &str) {
+    let mut columns = 0;
+    let mut taken = false;
+    for (index, segment) in ansi::segments(line) {
+        if let Segment::Grapheme(grapheme) = segment {
+            columns += display_width(grapheme);
+            if columns > width && taken {
+                return line.split_at(index);
+            }
+            taken = true;
+        }
+    }
+    (line, "")
+}
+
+/// Cut a string down to at most `width` columns.
+///
+/// If anything is cut off, the string ends in an ellipsis instead. Escape
+/// sequences in the part that is cut off are kept, so styles are still reset.
+pub fn truncate(text: &str, width: usize) -> String {
+    truncate_with(text, width, &Overflow::Truncate, "…")
+}
+
+/// Cut a string down to at most `width` columns, where `overflow` says.
+///
+/// The graphemes cut off are replaced by `ellipsis`, which is itself cut short
+/// when it is wider than `width`. Text that would wrap is cut at the end.
+/// Escape sequences in the part that is cut off are kept, so styles still
+/// apply to the rest of the string and are still reset.
+pub fn truncate_with(text: &str, width: usize, overflow: &Overflow, ellipsis: &str) -> String {
+    if display_width(text) <= width {
+        return String::from(text);
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let ellipsis_width = display_width(ellipsis);
+    let (ellipsis, budget) = if ellipsis_width > width {
+        (split_at_width(ellipsis, width).0, 0)
+    } else {
+        (ellipsis, width - ellipsis_width)
+    };
+    let (head_budget, tail_budget) = match overflow {
+        Overflow::Wrap | Overflow::Truncate => (budget, 0),
+        Overflow::TruncateMiddle => (budget - budget / 2, budget / 2),
+        Overflow::TruncateStart => (0, budget),
+    };
+
+    let segments = ansi::segments(text);
+    let widths = segments
+        .iter()
+        .filter_map(|(_, segment)| match segment {
+            Segment::Grapheme(grapheme) => Some(display_width(grapheme)),
+            Segment::Escape(_) => None,
+        })
+        .collect::<Vec<usize>>();
+    let head = fitting(widths.iter(), head_budget);
+    let tail = fitting(widths.iter().rev(), tail_budget).min(widths.len() - head);
+    let cut = head..widths.len() - tail;
+
+    let mut truncated = String::new();
+    let mut index = 0;
+    for (_, segment) in segments {
+        match segment {
+            Segment::Escape(escape) => truncated += escape,
+            Segment::Grapheme(grapheme) => {
+                if index == cut.start {
+                    truncated += ellipsis;
+                }
+                if !cut.contains(&index) {
+                    truncated += grapheme;
+                }
+                index += 1;
+            }
+        }
+    }
+    truncated
+}
+
+/// Helper function to count the graphemes, of the given widths, that fit in `budget` columns
+fn fitting<'a>(widths: impl Iterator<Item = &'a usize>, budget: usize) -> usize {
+    let mut columns = 0;
+    widths
+        .take_while(|width| {
+            columns += **width;
+            columns <= budget
+        })
+        .count()
 }
 
 /// Helper function to get the length of the longest line
-pub fn max_line_length(message: &String) -> usize {
+pub fn max_line_length(message: &str) -> usize {
     let mut max_length = 0;
     for line in message.lines() {
-        max_length = max(max_length, line.len())
+        max_length = max(max_length, display_width(line))
     }
     max_length
 }
 
+/// Repeat a piece of line until it is exactly `width` columns wide.
+///
+/// The last repetition is cut short between graphemes when the piece
+/// doesn't divide the width. A wide grapheme that would stick out past
+/// the end is replaced by spaces.
+pub fn repeat_to_width(piece: &str, width: usize) -> String {
+    let mut line = String::new();
+    write_repeated(&mut line, piece, width).expect("a String can always be written to");
+    line
+}
+
+/// Write a piece of line repeated to exactly `width` columns, see [repeat_to_width].
+pub fn write_repeated<W: fmt::Write>(out: &mut W, piece: &str, width: usize) -> fmt::Result {
+    let piece_width = display_width(piece);
+    if piece_width == 0 {
+        return write_whitespace(out, width);
+    }
+    // whole repetitions can be written without looking at the graphemes
+    let mut columns = 0;
+    while columns + piece_width <= width {
+        out.write_str(piece)?;
+        columns += piece_width;
+    }
+    for grapheme in piece.graphemes(true) {
+        let grapheme_width = display_width(grapheme);
+        if columns + grapheme_width > width {
+            break;
+        }
+        out.write_str(grapheme)?;
+        columns += grapheme_width;
+    }
+    write_whitespace(out, width - columns)
+}
+
 /// Helper function to get whitespace for padding
 pub fn gen_whitespace(num: usize) -> String {
-    (0..num).map(|_| " ").collect::<String>()
+    " ".repeat(num)
+}
+
+/// Write `num` spaces without allocating
+pub fn write_whitespace<W: fmt::Write>(out: &mut W, num: usize) -> fmt::Result {
+    const SPACES: &str = "                                ";
+    let mut left = num;
+    while left > 0 {
+        let chunk = left.min(SPACES.len());
+        out.write_str(&SPACES[..chunk])?;
+        left -= chunk;
+    }
+    Ok(())
+}
+
+/// Lets a box be written into an io::Write as if it were a fmt::Write,
+/// keeping the io error that stopped the writing.
+pub struct IoWriter<'a, W: io::Write> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<'a, W: io::Write> IoWriter<'a, W> {
+    pub fn new(inner: &'a mut W) -> IoWriter<'a, W> {
+        IoWriter { inner, error: None }
+    }
+
+    /// The io error that stopped the writing
+    pub fn into_error(self) -> io::Error {
+        self.error
+            .unwrap_or_else(|| io::Error::other("formatting the box failed"))
+    }
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.inner.write_all(text.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    /// Formatting with all horizontal padding on the left
+    fn format(max_width: usize, padding: usize, wrapping: Wrapping) -> Formatting {
+        let mut format = Formatting::new();
+        format.max_width = max_width;
+        format.padding_left = Some(padding);
+        format.padding_right = Some(0);
+        format.wrapping = wrapping;
+        format
+    }
 
     #[test]
     fn test_nomralize_lines() {
         let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
         let expected = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tem\npor incididunt ut labore et dolore magna aliqua.\n";
 
-        let normalized = normalize_lines(&String::from(message), 80, 3);
+        let normalized = normalize_lines(&String::from(message), &format(80, 3, Wrapping::Hard), 2);
         assert_eq!(expected, normalized);
     }
+
+    #[test]
+    fn test_normalize_lines_word_wrap() {
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        let expected = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod\ntempor incididunt ut labore et dolore magna aliqua.\n";
+
+        let normalized = normalize_lines(message, &format(80, 3, Wrapping::Word), 2);
+        assert_eq!(expected, normalized);
+    }
+
+    #[test]
+    fn test_display_width_ascii() {
+        assert_eq!(display_width("whatever"), 8);
+    }
+
+    #[test]
+    fn test_display_width_accented() {
+        assert_eq!(display_width("café déjà vu"), 12);
+    }
+
+    #[test]
+    fn test_display_width_east_asian_wide() {
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("ｆｕｌｌ"), 8);
+    }
+
+    #[test]
+    fn test_display_width_combining_marks() {
+        // "e" followed by U+0301 COMBINING ACUTE ACCENT
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("a\u{308}o\u{308}u\u{308}"), 3);
+    }
+
+    #[test]
+    fn test_display_width_zero_width_joiner() {
+        // family emoji joined with U+200D ZERO WIDTH JOINER
+        assert_eq!(display_width("👨\u{200d}👩\u{200d}👧"), 2);
+        assert_eq!(display_width("a\u{200d}b"), 2);
+    }
+
+    #[test]
+    fn test_display_width_emoji() {
+        assert_eq!(display_width("✅ ok"), 5);
+        assert_eq!(display_width("🚀"), 2);
+    }
+
+    #[test]
+    fn test_display_width_box_drawing() {
+        assert_eq!(display_width("┌──┐"), 4);
+    }
+
+    #[test]
+    fn test_max_line_length_wide() {
+        let message = "abc\n日本語\nxy";
+        assert_eq!(max_line_length(message), 6);
+    }
+
+    #[test]
+    fn test_split_at_width_wide() {
+        assert_eq!(split_at_width("日本語", 3), ("日", "本語"));
+        assert_eq!(split_at_width("日本語", 4), ("日本", "語"));
+    }
+
+    #[test]
+    fn test_split_at_width_combining_marks() {
+        assert_eq!(
+            split_at_width("e\u{301}e\u{301}", 1),
+            ("e\u{301}", "e\u{301}")
+        );
+    }
+
+    #[test]
+    fn test_normalize_lines_wide() {
+        let message = "日本語日本語";
+        let normalized = normalize_lines(message, &format(8, 2, Wrapping::Hard), 2);
+        assert_eq!("日本\n語日本語\n", normalized);
+    }
+
+    #[test]
+    fn test_split_at_width_grapheme_cluster() {
+        // "n" followed by U+0303 COMBINING TILDE must not be split apart
+        assert_eq!(split_at_width("an\u{303}o", 1), ("a", "n\u{303}o"));
+        assert_eq!(split_at_width("an\u{303}o", 2), ("an\u{303}", "o"));
+    }
+
+    #[test]
+    fn test_split_at_width_zero() {
+        assert_eq!(split_at_width("日本", 0), ("日", "本"));
+        assert_eq!(split_at_width("", 0), ("", ""));
+    }
+
+    #[test]
+    fn test_normalize_lines_multi_byte_split_point() {
+        // The byte offset 3 lands inside "é", this used to panic.
+        let message = "aaéééééééééé";
+        let normalized = normalize_lines(message, &format(8, 3, Wrapping::Hard), 2);
+        assert_eq!("aaé\nééé\néééééé\n", normalized);
+    }
+
+    #[test]
+    fn test_normalize_lines_padding_wider_than_max_width() {
+        let normalized = normalize_lines("abc", &format(2, 10, Wrapping::Hard), 2);
+        assert_eq!("a\nbc\n", normalized);
+        let normalized = normalize_lines("abc", &format(2, 10, Wrapping::Word), 2);
+        assert_eq!("a\nb\nc\n", normalized);
+    }
+
+    #[test]
+    fn test_normalize_lines_zero_max_width() {
+        let normalized = normalize_lines("ab", &format(0, 0, Wrapping::Hard), 2);
+        assert_eq!("a\nb\n", normalized);
+        let normalized = normalize_lines("ab", &format(0, 0, Wrapping::Word), 2);
+        assert_eq!("a\nb\n", normalized);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("whatever", 8), "whatever");
+        assert_eq!(truncate("whatever", 5), "what…");
+        assert_eq!(truncate("whatever", 1), "…");
+        assert_eq!(truncate("whatever", 0), "");
+    }
+
+    #[test]
+    fn test_truncate_wide() {
+        assert_eq!(truncate("日本語", 4), "日…");
+        assert_eq!(truncate("日本語", 5), "日本…");
+    }
+
+    #[test]
+    fn test_truncate_middle_and_start() {
+        let path = "/very/long/path/to/file.rs";
+        assert_eq!(
+            truncate_with(path, 17, &Overflow::TruncateMiddle, "…"),
+            "/very/lo…/file.rs"
+        );
+        assert_eq!(
+            truncate_with(path, 12, &Overflow::TruncateStart, "…"),
+            "…/to/file.rs"
+        );
+        assert_eq!(
+            truncate_with(path, 12, &Overflow::Truncate, "..."),
+            "/very/lon..."
+        );
+        assert_eq!(
+            truncate_with(path, 2, &Overflow::TruncateMiddle, "..."),
+            ".."
+        );
+        assert_eq!(
+            truncate_with("日本語", 4, &Overflow::TruncateStart, "…"),
+            "…語"
+        );
+    }
+
+    #[test]
+    fn test_truncate_start_keeps_escapes() {
+        assert_eq!(
+            truncate_with(
+                "\x1B[31mwhat\x1B[1mever\x1B[0m",
+                5,
+                &Overflow::TruncateStart,
+                "…"
+            ),
+            "\x1B[31m…\x1B[1mever\x1B[0m"
+        );
+    }
+
+    #[test]
+    fn test_normalize_lines_truncate() {
+        let mut format = format(10, 1, Wrapping::Word);
+        format.overflow = Overflow::TruncateMiddle;
+        let normalized = normalize_lines("abcdefghijklm\nabc", &format, 2);
+        assert_eq!("abc…klm\nabc\n", normalized);
+    }
+
+    #[test]
+    fn test_repeat_to_width() {
+        assert_eq!(repeat_to_width("─", 3), "───");
+        assert_eq!(repeat_to_width("=-", 5), "=-=-=");
+        assert_eq!(repeat_to_width("日", 5), "日日 ");
+        assert_eq!(repeat_to_width("─", 0), "");
+        assert_eq!(repeat_to_width("", 2), "  ");
+        assert_eq!(repeat_to_width("─", 70), "─".repeat(70));
+    }
+
+    #[test]
+    fn test_justify_line() {
+        assert_eq!(justify_line("a b c", 9), "a   b   c");
+        assert_eq!(justify_line("a b c", 8), "a   b  c");
+        assert_eq!(justify_line("日本 語", 8), "日本  語");
+    }
+
+    #[test]
+    fn test_justify_line_unchanged() {
+        assert_eq!(justify_line("whatever", 12), "whatever");
+        assert_eq!(justify_line("a b c", 4), "a b c");
+    }
+
+    #[test]
+    fn test_normalize_lines_justify() {
+        let mut format = format(20, 2, Wrapping::Word);
+        format.alignment = Alignment::Justify;
+        let normalized = normalize_lines("Lorem ipsum dolor sit amet\nconsectetur", &format, 2);
+        assert_eq!("Lorem    ipsum\ndolor sit amet\nconsectetur\n", normalized);
+    }
+
+    #[test]
+    fn test_display_width_ignores_escapes() {
+        assert_eq!(display_width("\x1B[31m┌──┐\x1B[0m"), 4);
+        assert_eq!(display_width("\x1B[1;38;2;1;2;3m日本\x1B[0m"), 4);
+    }
+
+    #[test]
+    fn test_write_whitespace() {
+        let mut spaces = String::new();
+        write_whitespace(&mut spaces, 70).unwrap();
+        assert_eq!(spaces, " ".repeat(70));
+    }
+
+    #[test]
+    fn test_expand_tabs() {
+        assert_eq!(expand_tabs("key\tvalue", 8), "key     value");
+        assert_eq!(expand_tabs("longer key\tvalue", 8), "longer key      value");
+        assert_eq!(expand_tabs("\ta\tb", 4), "    a   b");
+        assert_eq!(expand_tabs("日本\tx", 4), "日本    x");
+        assert_eq!(expand_tabs("\x1B[1mab\x1B[0m\tc", 4), "\x1B[1mab\x1B[0m  c");
+        assert_eq!(expand_tabs("a\tb", 0), "ab");
+    }
+
+    #[test]
+    fn test_split_around_escapes() {
+        assert_eq!(
+            split_at_width("\x1B[31mab\x1B[0mcd", 2),
+            ("\x1B[31mab\x1B[0m", "cd")
+        );
+        assert_eq!(split_at_width("\x1B[31mab", 0), ("\x1B[31ma", "b"));
+    }
+
+    #[test]
+    fn test_truncate_keeps_escapes() {
+        assert_eq!(
+            truncate("\x1B[1mBuild\x1B[0m Summary", 4),
+            "\x1B[1mBui…\x1B[0m"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_normalize_lines_total(
+            message in any::<String>(),
+            max_width in 0usize..200,
+            padding in 0usize..100,
+        ) {
+            normalize_lines(&message, &format(max_width, padding, Wrapping::Hard), 2);
+            normalize_lines(&message, &format(max_width, padding, Wrapping::Word), 2);
+        }
+
+        #[test]
+        fn prop_hard_wrap_keeps_content(
+            message in "\\PC*",
+            max_width in 0usize..100,
+            padding in 0usize..20,
+        ) {
+            let normalized = normalize_lines(&message, &format(max_width, padding, Wrapping::Hard), 2);
+            prop_assert_eq!(normalized.replace('\n', ""), message.lines().collect::<String>());
+        }
+
+        #[test]
+        fn prop_hard_wrap_splits_on_graphemes(
+            message in "\\PC*",
+            max_width in 0usize..100,
+            padding in 0usize..20,
+        ) {
+            let normalized = normalize_lines(&message, &format(max_width, padding, Wrapping::Hard), 2);
+            let graphemes = message.lines().flat_map(|line| line.graphemes(true)).count();
+            let wrapped_graphemes = normalized.lines().flat_map(|line| line.graphemes(true)).count();
+            prop_assert_eq!(graphemes, wrapped_graphemes);
+        }
+
+        #[test]
+        fn prop_word_wrap_fits_width(
+            message in "\\PC*",
+            max_width in 0usize..100,
+            padding in 0usize..20,
+        ) {
+            let width = max_width.saturating_sub(padding + 2);
+            let widest_grapheme = message.graphemes(true).map(display_width).max().unwrap_or(0);
+            let normalized = normalize_lines(&message, &format(max_width, padding, Wrapping::Word), 2);
+            for line in normalized.lines() {
+                prop_assert!(display_width(line) <= max(width, widest_grapheme));
+            }
+        }
+    }
 }
@@ -0,0 +1,267 @@
+/// Text embedded in the top or bottom line of the box
+use std::fmt;
+
+use crate::formatting::Position;
+use crate::helper;
+
+/// Stores the labels written into one horizontal line of the box.
+/// Each position holds at most one label.
+pub struct Labels {
+    pub left: Option<String>,
+    pub center: Option<String>,
+    pub right: Option<String>,
+    pub decoration_left: String,
+    pub decoration_right: String,
+}
+
+impl Labels {
+    /// Constructs labels with every position empty and a space either side of each label.
+    pub fn new() -> Labels {
+        Labels {
+            left: None,
+            center: None,
+            right: None,
+            decoration_left: String::from(" "),
+            decoration_right: String::from(" "),
+        }
+    }
+
+    /// Set the label at the given position
+    pub fn set(&mut self, position: &Position, text: &str) {
+        let slot = match position {
+            Position::Left => &mut self.left,
+            Position::Center => &mut self.center,
+            Position::Right => &mut self.right,
+        };
+        *slot = Some(String::from(text));
+    }
+
+    /// Remove and return the first label that is set, from left to right
+    pub fn take(&mut self) -> Option<String> {
+        self.left
+            .take()
+            .or_else(|| self.center.take())
+            .or_else(|| self.right.take())
+    }
+
+    /// Columns needed to draw every label in full.
+    ///
+    /// Labels are kept at least one line character away from the corners
+    /// and from each other.
+    pub fn width(&self) -> usize {
+        self.fitted_width(&self.texts())
+    }
+
+    /// Draw a horizontal line `length` columns wide with the labels written into it.
+    ///
+    /// When the labels don't fit, the widest label is truncated with an ellipsis
+    /// one column at a time until they do. If even empty labels, or labels that
+    /// take up no columns, don't fit, the line is drawn without them.
+    pub fn render(&self, horizontal: &str, length: usize) -> String {
+        let mut texts = self.texts();
+        while self.fitted_width(&texts) > length {
+            let widest = texts
+                .iter_mut()
+                .max_by_key(|(_, text)| helper::display_width(text))
+                .map(|(_, text)| text);
+            let width = widest
+                .as_ref()
+                .map_or(0, |text| helper::display_width(text));
+            match widest {
+                Some(text) if width > 0 => *text = helper::truncate(text, width - 1),
+                _ => return gen_line(horizontal, length),
+            }
+        }
+
+        let pieces = texts
+            .iter()
+            .map(|(position, text)| {
+                let piece = format!("{}{}{}", self.decoration_left, text, self.decoration_right);
+                (*position, piece)
+            })
+            .collect::<Vec<(&Position, String)>>();
+
+        let mut line = String::new();
+        let mut column = 0;
+        for (index, (position, piece)) in pieces.iter().enumerate() {
+            let piece_width = helper::display_width(piece);
+            let rest_width = pieces[index + 1..]
+                .iter()
+                .map(|(_, piece)| helper::display_width(piece) + 1)
+                .sum::<usize>();
+            let latest = length - 1 - rest_width - piece_width;
+            let start = match position {
+                Position::Left => 1,
+                Position::Center => (length - piece_width) / 2,
+                Position::Right => latest,
+            };
+            let start = start.max(column + 1).min(latest);
+            line += &gen_line(horizontal, start - column);
+            line += piece;
+            column = start + piece_width;
+        }
+        line += &gen_line(horizontal, length - column);
+        line
+    }
+
+    /// Write the line drawn by [render](#method.render) into `out`.
+    /// Lines without labels are written without building them first.
+    pub fn write<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        horizontal: &str,
+        length: usize,
+    ) -> fmt::Result {
+        if self.texts().is_empty() {
+            helper::write_repeated(out, horizontal, length)
+        } else {
+            out.write_str(&self.render(horizontal, length))
+        }
+    }
+
+    /// Labels that are set, from left to right
+    fn texts(&self) -> Vec<(&'static Position, String)> {
+        [
+            (&Position::Left, &self.left),
+            (&Position::Center, &self.center),
+            (&Position::Right, &self.right),
+        ]
+        .iter()
+        .filter_map(|(position, text)| text.as_ref().map(|text| (*position, text.clone())))
+        .collect()
+    }
+
+    /// Columns needed to draw the given labels
+    fn fitted_width(&self, texts: &[(&Position, String)]) -> usize {
+        if texts.is_empty() {
+            return 0;
+        }
+        texts
+            .iter()
+            .map(|(_, text)| self.decorated_width(text))
+            .sum::<usize>()
+            + texts.len()
+            + 1
+    }
+
+    fn decorated_width(&self, text: &str) -> usize {
+        helper::display_width(&self.decoration_left)
+            + helper::display_width(text)
+            + helper::display_width(&self.decoration_right)
+    }
+}
+
+/// Repeat the horizontal line piece to fill `length` columns
+fn gen_line(horizontal: &str, length: usize) -> String {
+    helper::repeat_to_width(horizontal, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_labels() {
+        let labels = Labels::new();
+        assert_eq!(labels.width(), 0);
+        assert_eq!(labels.render("─", 5), "─────");
+    }
+
+    #[test]
+    fn test_left_label() {
+        let mut labels = Labels::new();
+        labels.set(&Position::Left, "Title");
+        assert_eq!(labels.width(), 9);
+        assert_eq!(labels.render("─", 12), "─ Title ────");
+    }
+
+    #[test]
+    fn test_center_label() {
+        let mut labels = Labels::new();
+        labels.set(&Position::Center, "Title");
+        assert_eq!(labels.render("─", 13), "─── Title ───");
+        assert_eq!(labels.render("─", 12), "── Title ───");
+    }
+
+    #[test]
+    fn test_right_label() {
+        let mut labels = Labels::new();
+        labels.set(&Position::Right, "Title");
+        assert_eq!(labels.render("─", 12), "──── Title ─");
+    }
+
+    #[test]
+    fn test_all_labels() {
+        let mut labels = Labels::new();
+        labels.set(&Position::Left, "a");
+        labels.set(&Position::Center, "b");
+        labels.set(&Position::Right, "c");
+        assert_eq!(labels.width(), 13);
+        assert_eq!(labels.render("─", 13), "─ a ─ b ─ c ─");
+        assert_eq!(labels.render("─", 17), "─ a ─── b ─── c ─");
+    }
+
+    #[test]
+    fn test_decoration() {
+        let mut labels = Labels::new();
+        labels.decoration_left = String::from("┤ ");
+        labels.decoration_right = String::from(" ├");
+        labels.set(&Position::Left, "Title");
+        assert_eq!(labels.render("─", 12), "─┤ Title ├──");
+    }
+
+    #[test]
+    fn test_truncate_label() {
+        let mut labels = Labels::new();
+        labels.set(&Position::Left, "Build Summary");
+        assert_eq!(labels.render("─", 10), "─ Build… ─");
+    }
+
+    #[test]
+    fn test_truncate_widest_label() {
+        let mut labels = Labels::new();
+        labels.set(&Position::Left, "ab");
+        labels.set(&Position::Right, "abcdef");
+        assert_eq!(labels.render("─", 12), "─ ab ─ ab… ─");
+    }
+
+    #[test]
+    fn test_too_narrow_for_labels() {
+        let mut labels = Labels::new();
+        labels.set(&Position::Left, "Title");
+        assert_eq!(labels.render("─", 3), "───");
+        assert_eq!(labels.render("─", 0), "");
+    }
+
+    #[test]
+    fn test_zero_width_labels() {
+        for text in ["\u{301}", "\u{200B}", "\x1B[31m\x1B[0m"] {
+            for position in [Position::Left, Position::Center, Position::Right] {
+                let mut labels = Labels::new();
+                labels.set(&position, text);
+                assert_eq!(labels.render("─", 3), "───");
+            }
+        }
+    }
+
+    #[test]
+    fn test_write() {
+        let mut labels = Labels::new();
+        let mut line = String::new();
+        labels.write(&mut line, "─", 5).unwrap();
+        assert_eq!(line, "─────");
+
+        labels.set(&Position::Right, "Title");
+        line.clear();
+        labels.write(&mut line, "─", 12).unwrap();
+        assert_eq!(line, labels.render("─", 12));
+    }
+
+    #[test]
+    fn test_take() {
+        let mut labels = Labels::new();
+        labels.set(&Position::Right, "Title");
+        assert_eq!(labels.take(), Some(String::from("Title")));
+        assert_eq!(labels.width(), 0);
+    }
+}
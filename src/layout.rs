@@ -0,0 +1,394 @@
+/// Boxes drawn next to each other
+use std::fmt;
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+
+use crate::formatting::VerticalAlignment;
+use crate::helper;
+use crate::terminal;
+use crate::BoxBuilder;
+
+/// Layout builder struct that draws boxes side by side.
+///
+/// Boxes are placed left to right. When the next box would make the row
+/// wider than max_width it starts a new row of boxes below.
+/// ```
+/// use bauxite::{BoxBuilder, LayoutBuilder};
+///
+/// let layout = LayoutBuilder::new()
+///     .push_box(BoxBuilder::from("a").padding(0))
+///     .push_box(BoxBuilder::from("b\nc").padding(0))
+///     .gap(2);
+/// assert_eq!(
+///     layout.to_string(),
+///     "┌─┐  ┌─┐\n\
+///      │a│  │b│\n\
+///      └─┘  │c│\n\
+///      \x20    └─┘"
+/// );
+/// ```
+pub struct LayoutBuilder {
+    boxes: Vec<BoxBuilder>,
+    gap: usize,
+    vertical_alignment: VerticalAlignment,
+    equal_heights: bool,
+    max_width: usize,
+    fit_terminal: bool,
+    terminal_fd: i32,
+}
+
+impl LayoutBuilder {
+    /// Create a new layout with no boxes
+    pub fn new() -> LayoutBuilder {
+        LayoutBuilder {
+            boxes: Vec::new(),
+            gap: 1,
+            vertical_alignment: VerticalAlignment::Top,
+            equal_heights: false,
+            max_width: 80,
+            fit_terminal: false,
+            terminal_fd: 1,
+        }
+    }
+
+    /// Add a box to the right of the boxes already in the layout
+    pub fn push_box(mut self, boxed: BoxBuilder) -> Self {
+        self.boxes.push(boxed);
+        self
+    }
+
+    /// Set the number of columns between boxes
+    pub fn gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Set where boxes shorter than the tallest box in their row are placed
+    pub fn vertical_alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.vertical_alignment = alignment;
+        self
+    }
+
+    /// Stretch every box to the height of the tallest box in its row.
+    /// The extra lines are placed by the vertical alignment of each box.
+    pub fn equal_heights(mut self, equal: bool) -> Self {
+        self.equal_heights = equal;
+        self
+    }
+
+    /// Set the width a row of boxes may take before the next box starts a new row.
+    /// Boxes wider than this on their own are wrapped to fit it. 80 by default.
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    /// Use the width of the terminal in place of max_width, so rows of boxes wrap
+    /// where the terminal does.
+    ///
+    /// The width is found like [terminal_width](fn.terminal_width.html), from the
+    /// `COLUMNS` environment variable or the terminal on standard output.
+    pub fn fit_terminal(mut self, fit: bool) -> Self {
+        self.fit_terminal = fit;
+        self
+    }
+
+    /// Use the width of the terminal `output` is open on in place of max_width, like
+    /// [fit_terminal](struct.LayoutBuilder.html#method.fit_terminal).
+    #[cfg(unix)]
+    pub fn fit_terminal_of<T: AsRawFd>(mut self, output: &T) -> Self {
+        self.fit_terminal = true;
+        self.terminal_fd = output.as_raw_fd();
+        self
+    }
+
+    /// Layout to string.
+    /// Returns every row of boxes, one below the other
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        let mut layout = String::new();
+        self.write_layout(&mut layout, self.width())
+            .expect("a String can always be written to");
+        layout
+    }
+
+    /// The width a row of boxes may take
+    fn width(&self) -> usize {
+        if self.fit_terminal {
+            terminal::width_of(self.terminal_fd)
+        } else {
+            self.max_width
+        }
+    }
+
+    /// Write the rows of boxes into `out`, each row no wider than `max_width`
+    fn write_layout<W: fmt::Write>(&self, out: &mut W, max_width: usize) -> fmt::Result {
+        let rendered = self
+            .boxes
+            .iter()
+            .map(|boxed| self.render_box(boxed, max_width, 0))
+            .collect::<Vec<String>>();
+
+        let mut rows: Vec<Vec<usize>> = Vec::new();
+        let mut row_width = 0;
+        for (index, boxed) in rendered.iter().enumerate() {
+            let width = helper::max_line_length(boxed);
+            match rows.last_mut() {
+                Some(row) if row_width + self.gap + width <= max_width => {
+                    row.push(index);
+                    row_width += self.gap + width;
+                }
+                _ => {
+                    rows.push(vec![index]);
+                    row_width = width;
+                }
+            }
+        }
+
+        for (row_index, row) in rows.iter().enumerate() {
+            if row_index > 0 {
+                out.write_char('\n')?;
+            }
+            let height = row
+                .iter()
+                .map(|index| rendered[*index].lines().count())
+                .max()
+                .unwrap_or(0);
+            let blocks = row
+                .iter()
+                .map(|index| {
+                    if self.equal_heights {
+                        self.render_box(&self.boxes[*index], max_width, height)
+                    } else {
+                        rendered[*index].clone()
+                    }
+                })
+                .collect::<Vec<String>>();
+            self.write_blocks(out, &blocks, height)?;
+        }
+        Ok(())
+    }
+
+    /// Draw a box no wider than `layout_width`
+    fn render_box(&self, boxed: &BoxBuilder, layout_width: usize, height: usize) -> String {
+        let (max_width, min_width) = boxed.format.widths();
+        let available =
+            layout_width.saturating_sub(boxed.format.left_margin() + boxed.format.right_margin());
+        boxed.render(max_width.min(available), min_width.min(available), height)
+    }
+
+    /// Helper function to write the lines of each block next to each other
+    fn write_blocks<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        blocks: &[String],
+        height: usize,
+    ) -> fmt::Result {
+        let columns = blocks
+            .iter()
+            .map(|block| self.align_block(block, height))
+            .collect::<Vec<Vec<String>>>();
+        for line in 0..height {
+            if line > 0 {
+                out.write_char('\n')?;
+            }
+            for (index, column) in columns.iter().enumerate() {
+                if index > 0 {
+                    helper::write_whitespace(out, self.gap)?;
+                }
+                out.write_str(&column[line])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Helper function to pad a block with empty lines up to `height`
+    fn align_block(&self, block: &str, height: usize) -> Vec<String> {
+        let width = helper::max_line_length(block);
+        let lines = block.lines().count();
+        let space = height - lines;
+        let above = match self.vertical_alignment {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Middle => space / 2,
+            VerticalAlignment::Bottom => space,
+        };
+        let blank = helper::gen_whitespace(width);
+        let mut aligned = vec![blank.clone(); above];
+        aligned.extend(block.lines().map(|line| {
+            let fill = width - helper::display_width(line);
+            String::from(line) + &helper::gen_whitespace(fill)
+        }));
+        aligned.resize(height, blank);
+        aligned
+    }
+}
+
+/// Implement fmt for LayoutBuilder so we can use pass a LayoutBuilder to `println!` for printing
+impl fmt::Display for LayoutBuilder {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.write_layout(formatter, self.width())
+    }
+}
+
+impl Default for LayoutBuilder {
+    fn default() -> Self {
+        LayoutBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ansi;
+    use crate::AnsiColorCode;
+
+    fn small(message: &str) -> BoxBuilder {
+        BoxBuilder::from(message).padding(0)
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let expected = "┌─┐ ┌──┐\n\
+                        │a│ │bb│\n\
+                        └─┘ └──┘";
+        let layout = LayoutBuilder::new()
+            .push_box(small("a"))
+            .push_box(small("bb"));
+        assert_eq!(expected, layout.to_string());
+    }
+
+    #[test]
+    fn test_vertical_alignment() {
+        let top = "┌─┐ ┌─┐\n\
+                   │a│ │b│\n\
+                   └─┘ │c│\n\
+                   \x20   │d│\n\
+                   \x20   └─┘";
+        let middle = "\x20   ┌─┐\n\
+                      ┌─┐ │b│\n\
+                      │a│ │c│\n\
+                      └─┘ │d│\n\
+                      \x20   └─┘";
+        let bottom = "\x20   ┌─┐\n\
+                      \x20   │b│\n\
+                      ┌─┐ │c│\n\
+                      │a│ │d│\n\
+                      └─┘ └─┘";
+        let layout = |alignment| {
+            LayoutBuilder::new()
+                .push_box(small("a"))
+                .push_box(small("b\nc\nd"))
+                .vertical_alignment(alignment)
+                .to_string()
+        };
+        assert_eq!(top, layout(VerticalAlignment::Top));
+        assert_eq!(middle, layout(VerticalAlignment::Middle));
+        assert_eq!(bottom, layout(VerticalAlignment::Bottom));
+    }
+
+    #[test]
+    fn test_equal_heights() {
+        let expected = "┌─┐ ┌─┐\n\
+                        │a│ │b│\n\
+                        │ │ │c│\n\
+                        └─┘ └─┘";
+        let layout = LayoutBuilder::new()
+            .push_box(small("a"))
+            .push_box(small("b\nc"))
+            .equal_heights(true);
+        assert_eq!(expected, layout.to_string());
+    }
+
+    #[test]
+    fn test_wrap_to_new_row() {
+        let expected = "┌──┐ ┌──┐\n\
+                        │aa│ │bb│\n\
+                        └──┘ └──┘\n\
+                        ┌──┐\n\
+                        │cc│\n\
+                        └──┘";
+        let layout = LayoutBuilder::new()
+            .push_box(small("aa"))
+            .push_box(small("bb"))
+            .push_box(small("cc"))
+            .max_width(12);
+        assert_eq!(expected, layout.to_string());
+    }
+
+    #[test]
+    fn test_wide_box_fits_max_width() {
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+        let layout = LayoutBuilder::new()
+            .push_box(BoxBuilder::from(message))
+            .max_width(20);
+        for line in layout.to_string().lines() {
+            assert!(helper::display_width(line) <= 20);
+        }
+    }
+
+    #[test]
+    fn test_colored_boxes_line_up() {
+        let layout = LayoutBuilder::new()
+            .push_box(small("a").color(AnsiColorCode::Red))
+            .push_box(small("b\nc"));
+        let plain = LayoutBuilder::new()
+            .push_box(small("a"))
+            .push_box(small("b\nc"));
+        assert_eq!(ansi::strip_escapes(&layout.to_string()), plain.to_string());
+    }
+
+    #[test]
+    fn test_margins() {
+        let expected = "\x20   ┌─┐\n\
+                        ┌─┐ │b│\n\
+                        │a│ │c│\n\
+                        └─┘ └─┘\n\
+                        \x20      ";
+        let layout = LayoutBuilder::new()
+            .push_box(small("a").margin_top(1))
+            .push_box(small("b\nc").margin_bottom(1));
+        assert_eq!(expected, layout.to_string());
+    }
+
+    #[test]
+    fn test_fit_terminal() {
+        let expected = "┌──┐ ┌──┐\n\
+                        │aa│ │bb│\n\
+                        └──┘ └──┘\n\
+                        ┌──┐\n\
+                        │cc│\n\
+                        └──┘";
+        let layout = LayoutBuilder::new()
+            .push_box(small("aa"))
+            .push_box(small("bb"))
+            .push_box(small("cc"))
+            .fit_terminal(true);
+        let mut rendered = String::new();
+        layout.write_layout(&mut rendered, 12).unwrap();
+        assert_eq!(expected, rendered);
+        assert_eq!(layout.width(), terminal::width());
+        assert_eq!(layout.fit_terminal(false).max_width(12).width(), 12);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fit_terminal_of() {
+        let output = std::fs::File::open("Cargo.toml").unwrap();
+        let layout = LayoutBuilder::new().fit_terminal_of(&output);
+        assert_eq!(layout.terminal_fd, output.as_raw_fd());
+        assert_eq!(layout.width(), terminal::width_of(output.as_raw_fd()));
+    }
+
+    #[test]
+    fn test_display() {
+        let layout = LayoutBuilder::new()
+            .push_box(small("a").color(AnsiColorCode::Red))
+            .push_box(small("b\nc"));
+        assert_eq!(format!("{}", layout), layout.to_string());
+    }
+
+    #[test]
+    fn test_empty_layout() {
+        assert_eq!(LayoutBuilder::new().to_string(), "");
+    }
+}
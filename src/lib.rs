@@ -21,49 +21,132 @@
 //! ```
 
 use std::fmt;
+use std::io;
+use std::iter;
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
 
+mod ansi;
 mod color;
 mod formatting;
+mod grid;
 mod helper;
+mod label;
+mod layout;
 mod lines;
+mod table;
+mod terminal;
+mod wrap;
 
+use self::color::Styles;
 use self::formatting::Formatting;
 
-pub use self::formatting::Alignment;
+pub use self::formatting::{
+    Alignment, Overflow, Position, TerminalFit, VerticalAlignment, VerticalOverflow, Wrapping,
+};
 pub use color::ansi_color_codes::AnsiColorCode;
+pub use color::attribute::Attribute;
 pub use color::rgb_color::RgbColor;
+pub use grid::{GridBuilder, GridError};
+pub use layout::LayoutBuilder;
 pub use lines::line_type::LineType;
+pub use lines::{Lines, LinesError};
+pub use table::TableBuilder;
+pub use terminal::width as terminal_width;
+
+/// What is drawn inside a box
+enum Content {
+    /// Text that is wrapped to fit the box
+    Text(String),
+    /// Another box, drawn whole inside this one
+    Box(Box<BoxBuilder>),
+}
+
+/// A section of the box, wrapped to fit and ready to draw
+struct Section<'a> {
+    format: &'a Formatting,
+    message: String,
+    /// The wrapped paragraphs of justified text, spread out once the width of the box is known
+    justified: Option<Vec<Vec<String>>>,
+    top_padding: usize,
+    bottom_padding: usize,
+}
+
+impl Section<'_> {
+    /// Lines of content in the section
+    fn lines(&self) -> usize {
+        self.message.lines().count()
+    }
+
+    /// Lines the section takes, with its padding
+    fn rows(&self) -> usize {
+        self.top_padding + self.lines() + self.bottom_padding
+    }
+}
 
 /// Box builder struct that represents your formatted line box.
 pub struct BoxBuilder {
-    message: String,
+    content: Content,
     format: Formatting,
     lines: lines::Lines,
-    color: color::LineColor,
+    sides: lines::Sides,
+    color: color::BoxColor,
+    title: label::Labels,
+    caption: label::Labels,
+    sections: Vec<BoxBuilder>,
 }
 
 impl BoxBuilder {
     /// Create a new boxed message from a String
     pub fn new(message: String) -> BoxBuilder {
         BoxBuilder {
-            message: message,
+            content: Content::Text(message),
             format: Formatting::new(),
             lines: lines::Lines::new(),
-            color: color::LineColor::new(),
+            sides: lines::Sides::new(LineType::Basic),
+            color: color::BoxColor::new(),
+            title: label::Labels::new(),
+            caption: label::Labels::new(),
+            sections: Vec::new(),
         }
     }
 
     /// Create new boxed message from a str
     pub fn from(message: &str) -> BoxBuilder {
         BoxBuilder {
-            message: String::from(message),
+            content: Content::Text(String::from(message)),
             format: Formatting::new(),
             lines: lines::Lines::new(),
-            color: color::LineColor {
-                ansi: None,
-                rgb: None,
-                color8: None,
-            },
+            sides: lines::Sides::new(LineType::Basic),
+            color: color::BoxColor::new(),
+            title: label::Labels::new(),
+            caption: label::Labels::new(),
+            sections: Vec::new(),
+        }
+    }
+
+    /// Create a box with another box drawn inside it.
+    ///
+    /// The inner box keeps its shape and its own colors. It is wrapped to fit
+    /// within the max_width of the outer box, less the outer lines and padding.
+    /// ```
+    /// use bauxite::{BoxBuilder, LineType};
+    ///
+    /// let inner = BoxBuilder::from("whatever").padding(0);
+    /// let outer = BoxBuilder::from_box(inner).line_type(LineType::Double).padding(1);
+    /// assert_eq!(
+    ///     outer.to_string(),
+    ///     "╔════════════╗\n\
+    ///      ║ ┌────────┐ ║\n\
+    ///      ║ │whatever│ ║\n\
+    ///      ║ └────────┘ ║\n\
+    ///      ╚════════════╝"
+    /// );
+    /// ```
+    pub fn from_box(inner: BoxBuilder) -> BoxBuilder {
+        BoxBuilder {
+            content: Content::Box(Box::new(inner)),
+            ..BoxBuilder::from("")
         }
     }
 
@@ -79,12 +162,121 @@ impl BoxBuilder {
         self
     }
 
+    /// Set where the content is placed when the box is taller than it using
+    /// [VerticalAlignment](enum.VerticalAlignment.html), at the top by default.
+    ///
+    /// The extra rows are added to `padding_top` and `padding_bottom`. In a box
+    /// with sections they go to the last section.
+    pub fn vertical_alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.format.vertical_alignment = alignment;
+        self
+    }
+
+    /// Set how lines wider than the box are wrapped using [Wrapping](enum.Wrapping.html)
+    pub fn wrapping(mut self, wrapping: Wrapping) -> Self {
+        self.format.wrapping = wrapping;
+        self
+    }
+
+    /// Set what happens to lines wider than the box using [Overflow](enum.Overflow.html).
+    ///
+    /// Lines wrap by default. Truncated lines are cut to the width of the box
+    /// and keep one line each, for example a file path in a status line.
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.format.overflow = overflow;
+        self
+    }
+
+    /// Set the text left in place of what is cut off truncated lines, "…" by default
+    pub fn ellipsis(mut self, ellipsis: &str) -> Self {
+        self.format.ellipsis = String::from(ellipsis);
+        self
+    }
+
     /// Set the maximum width of the box before lines should wrap
     pub fn max_width(mut self, width: usize) -> Self {
         self.format.max_width = width;
         self
     }
 
+    /// Set the exact width of the box, overriding `max_width`, `min_width` and terminal fitting.
+    ///
+    /// The box is never narrower than its lines, its padding and one column of content,
+    /// so a smaller width is raised to that.
+    pub fn width(mut self, width: usize) -> Self {
+        self.format.width = Some(width);
+        self
+    }
+
+    /// Set the width the box is stretched to when its content is narrower.
+    /// The box still stays within `max_width` when that is narrower.
+    pub fn min_width(mut self, width: usize) -> Self {
+        self.format.min_width = width;
+        self
+    }
+
+    /// Set the exact height of the box in lines, overriding `min_height`.
+    ///
+    /// Content too tall for the box is cut short, see
+    /// [vertical_overflow](struct.BoxBuilder.html#method.vertical_overflow).
+    /// Padding is given up before any content is cut, and the box widens up to
+    /// `max_width` to fit the indicator. The box is never shorter than its top and
+    /// bottom lines, and the indicator's line when there is one.
+    pub fn height(mut self, height: usize) -> Self {
+        self.format.height = Some(height);
+        self
+    }
+
+    /// Set the height the box is stretched to when its content is shorter.
+    /// The extra lines are placed by the vertical alignment of the box.
+    pub fn min_height(mut self, height: usize) -> Self {
+        self.format.min_height = height;
+        self
+    }
+
+    /// Set what happens to content too tall for the height of the box using
+    /// [VerticalOverflow](enum.VerticalOverflow.html), an indicator by default
+    pub fn vertical_overflow(mut self, overflow: VerticalOverflow) -> Self {
+        self.format.vertical_overflow = overflow;
+        self
+    }
+
+    /// Size the box to the terminal using [TerminalFit](enum.TerminalFit.html).
+    ///
+    /// Either way of fitting overrides `max_width`, though an exact `width` still wins.
+    /// The width is read from the `COLUMNS` environment variable, or asked of the
+    /// terminal on standard output, and is 80 when standard output isn't a terminal.
+    /// For a box written anywhere else, such as standard error, use
+    /// [fit_terminal_of](struct.BoxBuilder.html#method.fit_terminal_of).
+    pub fn fit_terminal(mut self, fit: TerminalFit) -> Self {
+        self.format.terminal_fit = Some(fit);
+        self
+    }
+
+    /// Size the box to the terminal `output` is open on, like
+    /// [fit_terminal](struct.BoxBuilder.html#method.fit_terminal).
+    /// ```
+    /// use bauxite::{BoxBuilder, TerminalFit};
+    ///
+    /// let boxed_message = BoxBuilder::from("warning").fit_terminal_of(TerminalFit::Fill, &std::io::stderr());
+    /// eprintln!("{}", boxed_message);
+    /// ```
+    #[cfg(unix)]
+    pub fn fit_terminal_of<T: AsRawFd>(mut self, fit: TerminalFit, output: &T) -> Self {
+        self.format.terminal_fit = Some(fit);
+        self.format.terminal_fd = output.as_raw_fd();
+        self
+    }
+
+    /// Set the number of columns between tab stops, 8 by default.
+    ///
+    /// Tabs in the message are replaced with spaces up to the next tab stop
+    /// before the message is measured, so tab separated columns stay aligned.
+    pub fn tab_width(mut self, width: usize) -> Self {
+        self.format.tab_width = width;
+        self
+    }
+
     /// Set the padding on the bottom, overrides the global bottom padding
     pub fn padding_bottom(mut self, pad: usize) -> Self {
         self.format.padding_bottom = Some(pad);
@@ -109,165 +301,715 @@ impl BoxBuilder {
         self
     }
 
-    /// Set the type of lines to draw using [LineType](enum.LineType.html)
+    /// Set the global margin around the box.
+    ///
+    /// Margins are left blank outside the lines of the box, and are never colored.
+    /// Like padding, the rows above and below the box are half the margin. The rows
+    /// are filled with spaces, so they aren't lost when the box is split into lines.
+    /// Sections share the lines of the box they are in, so their margins are ignored.
+    /// The margin is part of `max_width` and of the terminal width, so the box
+    /// is drawn narrower to leave room for it.
+    pub fn margin(mut self, margin: usize) -> Self {
+        self.format.margin = margin;
+        self
+    }
+
+    /// Set the margin on the top, overrides the global top margin
+    pub fn margin_top(mut self, margin: usize) -> Self {
+        self.format.margin_top = Some(margin);
+        self
+    }
+
+    /// Set the margin on the bottom, overrides the global bottom margin
+    pub fn margin_bottom(mut self, margin: usize) -> Self {
+        self.format.margin_bottom = Some(margin);
+        self
+    }
+
+    /// Set the margin on the left, overrides the global left margin
+    pub fn margin_left(mut self, margin: usize) -> Self {
+        self.format.margin_left = Some(margin);
+        self
+    }
+
+    /// Set the margin on the right, overrides the global right margin
+    pub fn margin_right(mut self, margin: usize) -> Self {
+        self.format.margin_right = Some(margin);
+        self
+    }
+
+    /// Set a title to write into the top line of the box.
+    ///
+    /// The box widens to fit the title, up to `max_width`. Titles that still
+    /// don't fit are cut short with an ellipsis.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title.take();
+        self.title.set(&self.format.title_position, title);
+        self
+    }
+
+    /// Set where the title is placed in the top line using [Position](enum.Position.html)
+    pub fn title_position(mut self, position: Position) -> Self {
+        if let Some(title) = self.title.take() {
+            self.title.set(&position, &title);
+        }
+        self.format.title_position = position;
+        self
+    }
+
+    /// Set the characters drawn either side of the title, a space by default.
+    ///
+    /// For example `.title_decoration("┤ ", " ├")` draws `┌─┤ Title ├─┐`.
+    pub fn title_decoration(mut self, left: &str, right: &str) -> Self {
+        self.title.decoration_left = String::from(left);
+        self.title.decoration_right = String::from(right);
+        self
+    }
+
+    /// Set a caption to write into the left of the bottom line of the box.
+    ///
+    /// Captions widen the box and are cut short the same way as the title.
+    pub fn caption_left(mut self, caption: &str) -> Self {
+        self.caption.set(&Position::Left, caption);
+        self
+    }
+
+    /// Set a caption to write into the center of the bottom line of the box
+    pub fn caption_center(mut self, caption: &str) -> Self {
+        self.caption.set(&Position::Center, caption);
+        self
+    }
+
+    /// Set a caption to write into the right of the bottom line of the box
+    pub fn caption_right(mut self, caption: &str) -> Self {
+        self.caption.set(&Position::Right, caption);
+        self
+    }
+
+    /// Set the characters drawn either side of each caption, a space by default
+    pub fn caption_decoration(mut self, left: &str, right: &str) -> Self {
+        self.caption.decoration_left = String::from(left);
+        self.caption.decoration_right = String::from(right);
+        self
+    }
+
+    /// Add a section below the message, separated from it by a dividing line.
+    ///
+    /// The section is drawn with the message, alignment, padding and wrapping
+    /// of the given box, and everything else comes from this box. All sections
+    /// share the width of the box.
+    /// ```
+    /// use bauxite::{Alignment, BoxBuilder};
+    ///
+    /// let boxed_message = BoxBuilder::from("Header")
+    ///     .alignment(Alignment::Center)
+    ///     .padding(1)
+    ///     .section(BoxBuilder::from("body text").padding(1));
+    /// assert_eq!(
+    ///     boxed_message.to_string(),
+    ///     "┌───────────┐\n\
+    ///      │  Header   │\n\
+    ///      ├───────────┤\n\
+    ///      │ body text │\n\
+    ///      └───────────┘"
+    /// );
+    /// ```
+    pub fn section(mut self, section: BoxBuilder) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Set the type of lines to draw using [LineType](enum.LineType.html).
+    /// Every piece is replaced, including any set with `lines` or the `line_type_*` calls.
     pub fn line_type(mut self, line_type: LineType) -> Self {
-        self.lines = lines::resolve_line_type(line_type);
+        self.sides = lines::Sides::new(line_type);
+        self.lines = self.sides.resolve();
+        self
+    }
+
+    /// Set the type of line drawn along the top of the box.
+    ///
+    /// Corners join the lines of each side where Unicode has a glyph for it,
+    /// so a `LineType::Double` top over `LineType::Basic` sides starts with `╒`.
+    /// Only the pieces along the top are replaced, along with the dividers inside
+    /// the box, so lines set earlier with `lines` are kept on the other sides.
+    pub fn line_type_top(mut self, line_type: LineType) -> Self {
+        self.sides.set(lines::Side::Top, line_type, &mut self.lines);
+        self
+    }
+
+    /// Set the type of line drawn along the bottom of the box.
+    /// Only the pieces along the bottom are replaced.
+    pub fn line_type_bottom(mut self, line_type: LineType) -> Self {
+        self.sides
+            .set(lines::Side::Bottom, line_type, &mut self.lines);
+        self
+    }
+
+    /// Set the type of line drawn down the left of the box.
+    /// Only the pieces along the left are replaced, along with the dividers inside the box.
+    pub fn line_type_left(mut self, line_type: LineType) -> Self {
+        self.sides
+            .set(lines::Side::Left, line_type, &mut self.lines);
         self
     }
 
-    /// Sets 8 bit color code.
+    /// Set the type of line drawn down the right of the box.
+    /// Only the pieces along the right are replaced.
+    pub fn line_type_right(mut self, line_type: LineType) -> Self {
+        self.sides
+            .set(lines::Side::Right, line_type, &mut self.lines);
+        self
+    }
+
+    /// Set the lines to draw the box with using [Lines](struct.Lines.html).
+    ///
+    /// Every piece is replaced. Calls apply in order, so a `line_type_*` call made
+    /// afterwards replaces the pieces along its side and keeps the rest, unless the
+    /// rest are a different width from box drawing lines, when every piece is replaced.
+    /// Returns an error if the lines wouldn't make a rectangular box.
+    pub fn lines(mut self, lines: Lines) -> Result<Self, LinesError> {
+        lines.validate()?;
+        self.lines = lines;
+        Ok(self)
+    }
+
+    /// Sets 8 bit color code of the lines.
     ///
     /// 0-7 are standard colors
     /// 8-15 are high intensity colors
     /// 16-231 are defined by 16 + 36 x r + 6 x g + b (0 <= r, g, b <= 5)
     /// 232-255 are grayscale from black to white in 24 steps
     pub fn color_8(mut self, color: u8) -> Self {
-        self.color.rgb = None;
-        self.color.ansi = None;
-        self.color.color8 = Some(color);
+        self.color.border = color::LineColor::from_8(color);
         self
     }
 
-    /// Basic RGB colors.
+    /// Basic RGB colors of the lines.
     pub fn color_rgb(mut self, red: u8, green: u8, blue: u8) -> Self {
-        self.color.ansi = None;
-        self.color.color8 = None;
-        self.color.rgb = Some(RgbColor {
-            red: red,
-            green: green,
-            blue: blue,
-        });
+        self.color.border = color::LineColor::from_rgb(RgbColor { red, green, blue });
         self
     }
 
-    /// Simplest ANSI color codes defind by AnsiColorCode enumerated type.
+    /// Simplest ANSI color codes of the lines defind by AnsiColorCode enumerated type.
     pub fn color(mut self, code: AnsiColorCode) -> Self {
-        self.color.rgb = None;
-        self.color.color8 = None;
-        self.color.ansi = Some(code);
+        self.color.border = color::LineColor::from_ansi(code);
+        self
+    }
+
+    /// Sets 8 bit color code of the message text, see [color_8](#method.color_8).
+    pub fn text_color_8(mut self, color: u8) -> Self {
+        self.color.text = color::LineColor::from_8(color);
+        self
+    }
+
+    /// Basic RGB colors of the message text.
+    pub fn text_color_rgb(mut self, red: u8, green: u8, blue: u8) -> Self {
+        self.color.text = color::LineColor::from_rgb(RgbColor { red, green, blue });
+        self
+    }
+
+    /// Simplest ANSI color codes of the message text.
+    pub fn text_color(mut self, code: AnsiColorCode) -> Self {
+        self.color.text = color::LineColor::from_ansi(code);
+        self
+    }
+
+    /// Sets 8 bit color code filling the box, see [color_8](#method.color_8).
+    pub fn background_color_8(mut self, color: u8) -> Self {
+        self.color.background = color::LineColor::from_8(color);
+        self
+    }
+
+    /// Basic RGB colors filling the box.
+    pub fn background_color_rgb(mut self, red: u8, green: u8, blue: u8) -> Self {
+        self.color.background = color::LineColor::from_rgb(RgbColor { red, green, blue });
+        self
+    }
+
+    /// Simplest ANSI color codes filling the box.
+    pub fn background_color(mut self, code: AnsiColorCode) -> Self {
+        self.color.background = color::LineColor::from_ansi(code);
+        self
+    }
+
+    /// Add a text attribute to the lines using [Attribute](enum.Attribute.html)
+    pub fn attribute(mut self, attribute: Attribute) -> Self {
+        if !self.color.border_attributes.contains(&attribute) {
+            self.color.border_attributes.push(attribute);
+        }
+        self
+    }
+
+    /// Add a text attribute to the message using [Attribute](enum.Attribute.html)
+    pub fn text_attribute(mut self, attribute: Attribute) -> Self {
+        if !self.color.text_attributes.contains(&attribute) {
+            self.color.text_attributes.push(attribute);
+        }
         self
     }
 
     /// Boxed message to string.
     /// Returns the full line boxed message
+    #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
-        let format = &self.format;
-        let right_padding = format.padding_right.unwrap_or(format.padding);
-        let left_padding = format.padding_left.unwrap_or(format.padding);
-        let total_horizontal_pad = right_padding + left_padding;
-
-        let normalized_message =
-            helper::normalize_lines(&self.message, format.max_width, total_horizontal_pad);
-        let max_line_length = helper::max_line_length(&normalized_message);
-
-        // wrap the message in the box
-        let mut boxed_message = self.gen_top(max_line_length + right_padding + left_padding);
-        boxed_message += &self.gen_top_padding(max_line_length + total_horizontal_pad);
-        boxed_message += &self.wrap_lines(&normalized_message, max_line_length);
-        boxed_message += &self.gen_bottom_padding(max_line_length + right_padding + left_padding);
-        boxed_message += &self.gen_bottom(max_line_length + left_padding + right_padding);
+        let (max_width, min_width) = self.format.widths();
+        self.render(max_width, min_width, 0)
+    }
+
+    /// Write the box into `out` a piece at a time, without building it as a String first.
+    /// ```
+    /// use bauxite::BoxBuilder;
+    ///
+    /// let mut boxed_message = String::new();
+    /// BoxBuilder::from("whatever")
+    ///     .padding(0)
+    ///     .render_to(&mut boxed_message)
+    ///     .unwrap();
+    /// assert_eq!(boxed_message, "┌────────┐\n│whatever│\n└────────┘");
+    /// ```
+    pub fn render_to<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let (max_width, min_width) = self.format.widths();
+        self.write_box(out, max_width, min_width, 0)
+    }
+
+    /// Write the box into an io::Write, such as stdout or a file, a piece at a time.
+    ///
+    /// Each piece is written on its own, so wrap unbuffered writers in a `BufWriter`.
+    pub fn write_to<W: io::Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut writer = helper::IoWriter::new(out);
+        self.render_to(&mut writer).map_err(|_| writer.into_error())
+    }
+
+    /// Draw the box between `min_width` and `max_width` wide and at least `height` lines tall
+    fn render(&self, max_width: usize, min_width: usize, height: usize) -> String {
+        let mut boxed_message = String::new();
+        self.write_box(&mut boxed_message, max_width, min_width, height)
+            .expect("a String can always be written to");
         boxed_message
     }
 
-    /// Helper function to build the top of the box
-    fn gen_top(&self, length: usize) -> String {
-        let vertical_line = (0..length)
-            .map(|_| self.lines.horizontal.clone())
-            .collect::<String>();
-        self.color.wrap_color(format!(
-            "{}{}{}\n",
-            self.lines.top_left, vertical_line, self.lines.top_right
-        ))
-    }
-
-    /// Helper function to build the bottom of the box
-    fn gen_bottom(&self, length: usize) -> String {
-        let vertical_line = (0..length)
-            .map(|_| self.lines.horizontal.clone())
-            .collect::<String>();
-        self.color.wrap_color(format!(
-            "{}{}{}",
-            self.lines.bottom_left, vertical_line, self.lines.bottom_right
-        ))
-    }
-
-    /// Wrap the message with the box on it's left and right
-    fn wrap_lines(&self, message: &String, max_length: usize) -> String {
-        message
-            .lines()
-            .map(|line| {
-                let left_padding = self.gen_left_padding(line.len(), &max_length);
-                let right_padding = self.gen_right_padding(line.len(), &max_length);
-                self.color.wrap_color(format!(
-                    "{}{}{}{}{}\n",
-                    self.lines.vertical, left_padding, line, right_padding, self.lines.vertical
-                ))
+    /// Write the box between `min_width` and `max_width` wide and at least `height` lines tall,
+    /// where the width leaves out the margins and the height includes them.
+    /// Extra lines are left empty around the content of the last section.
+    fn write_box<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        max_width: usize,
+        min_width: usize,
+        height: usize,
+    ) -> fmt::Result {
+        let mut sections = self.normalize_sections(max_width);
+        let length = sections
+            .iter()
+            .map(|section| {
+                helper::max_line_length(&section.message)
+                    + section.format.left_padding()
+                    + section.format.right_padding()
             })
-            .collect::<String>()
-    }
+            .max()
+            .unwrap_or(0);
 
-    /// Helper function to to_string padding left of the content
-    fn gen_left_padding(&self, line_length: usize, max_length: &usize) -> String {
-        let padding = match self.format.alignment {
-            Alignment::Left => self.format.padding,
-            Alignment::Right => self.format.padding + max_length - line_length,
+        // plan which lines of content fit the height, and the indicator that replaces the rest
+        let (max_height, min_height) = self.format.heights();
+        let space = max_height.map(|height| height.saturating_sub(2));
+        let kept = space.and_then(|space| self.kept_lines(&sections, space));
+        let indicator = match (kept, &self.format.vertical_overflow) {
+            (Some(kept), VerticalOverflow::Indicate) => {
+                let (index, _) = Self::cut_point(&sections, kept);
+                let format = sections[index].format;
+                let padding = format.left_padding() + format.right_padding();
+                let space = max_width.saturating_sub(self.lines.edge_width() + padding);
+                let hidden = sections.iter().map(Section::lines).sum::<usize>() - kept;
+                Some((Self::indicator(hidden, space), padding))
+            }
+            _ => None,
+        };
+        let length = match &indicator {
+            Some((indicator, padding)) => length.max(helper::display_width(indicator) + padding),
+            None => length,
         };
-        helper::gen_whitespace(padding)
+
+        // widen the box for the title and captions, as long as it stays within max_width
+        let label_width = self
+            .title
+            .width()
+            .max(self.caption.width())
+            .min(max_width.saturating_sub(self.lines.edge_width()));
+        let length = length.max(label_width).max(
+            min_width
+                .min(max_width)
+                .saturating_sub(self.lines.edge_width()),
+        );
+
+        // spread justified text across the box, which may be wider than the text
+        for section in sections.iter_mut() {
+            if let Some(paragraphs) = section.justified.take() {
+                let width = length - section.format.left_padding() - section.format.right_padding();
+                section.message = helper::join_paragraphs(&paragraphs, &Alignment::Justify, width);
+            }
+        }
+
+        if let Some(space) = space {
+            let indicator = indicator.map(|(indicator, _)| indicator);
+            Self::fit_height(&mut sections, space, kept, indicator);
+        }
+        let rows = Self::count_rows(&sections);
+        let margin_rows = self.format.top_margin() + self.format.bottom_margin();
+        let extra_rows = height
+            .saturating_sub(margin_rows)
+            .max(min_height)
+            .saturating_sub(rows);
+
+        // wrap each section in the box
+        let styles = self.color.styles();
+        let margin_width = self.format.left_margin()
+            + self.lines.edge_width()
+            + length
+            + self.format.right_margin();
+        for _ in 0..self.format.top_margin() {
+            helper::write_whitespace(out, margin_width)?;
+            out.write_char('\n')?;
+        }
+        self.write_top(out, &styles, length)?;
+        for (index, section) in sections.iter().enumerate() {
+            if index > 0 {
+                self.write_divider(out, &styles, length)?;
+            }
+            let format = section.format;
+            let max_line_length = length - format.left_padding() - format.right_padding();
+            let (above, below) = if index + 1 == sections.len() {
+                self.format.vertical_space(extra_rows)
+            } else {
+                (0, 0)
+            };
+            self.write_blank_rows(out, &styles, section.top_padding + above, length)?;
+            self.write_lines(out, &styles, format, &section.message, max_line_length)?;
+            self.write_blank_rows(out, &styles, below + section.bottom_padding, length)?;
+        }
+        self.write_bottom(out, &styles, length)?;
+        for _ in 0..self.format.bottom_margin() {
+            out.write_char('\n')?;
+            helper::write_whitespace(out, margin_width)?;
+        }
+        Ok(())
+    }
+
+    /// Helper function to count the lines of the box, from the top line to the bottom line
+    fn count_rows(sections: &[Section]) -> usize {
+        Self::section_rows(sections) + 2
+    }
+
+    /// Helper function to count the lines between the top line and the bottom line
+    fn section_rows(sections: &[Section]) -> usize {
+        sections.iter().map(Section::rows).sum::<usize>() + sections.len().saturating_sub(1)
     }
 
-    /// Helper function to to_string padding right of the content
-    fn gen_right_padding(&self, line_length: usize, max_length: &usize) -> String {
-        let padding = match self.format.alignment {
-            Alignment::Right => self.format.padding,
-            Alignment::Left => self.format.padding + max_length - line_length,
+    /// Helper function to find how many lines of content fit in `space` lines between
+    /// the top and bottom lines, when not all of them do.
+    ///
+    /// Padding is given up before any content, so it is left out of the count.
+    fn kept_lines(&self, sections: &[Section], space: usize) -> Option<usize> {
+        let total = sections.iter().map(Section::lines).sum::<usize>();
+        if total == 0 || total + sections.len() - 1 <= space {
+            return None;
+        }
+        let indicate = match self.format.vertical_overflow {
+            VerticalOverflow::Clip => false,
+            VerticalOverflow::Indicate => true,
         };
-        helper::gen_whitespace(padding)
-    }
-
-    /// Helper function to to_string top and bottom padding of the box
-    fn gen_top_padding(&self, length: usize) -> String {
-        let top_padding = self.format.padding_top.unwrap_or(self.format.padding / 2);
-        (0..top_padding)
-            .map(|_| {
-                format!(
-                    "{}{}{}\n",
-                    self.lines.vertical,
-                    helper::gen_whitespace(length),
-                    self.lines.vertical
-                )
-            })
-            .collect::<String>()
-    }
-
-    /// Helper function to to_string top and bottom padding of the box
-    fn gen_bottom_padding(&self, length: usize) -> String {
-        let bottom_padding = self
-            .format
-            .padding_bottom
-            .unwrap_or(self.format.padding / 2);
-        (0..bottom_padding)
-            .map(|_| {
-                format!(
-                    "{}{}{}\n",
-                    self.lines.vertical,
-                    helper::gen_whitespace(length),
-                    self.lines.vertical
-                )
+        // the most lines of content that fit, or none at all
+        let kept = (0..total)
+            .rev()
+            .find(|kept| Self::clipped_rows(sections, *kept, indicate) <= space)
+            .unwrap_or(0);
+        Some(kept)
+    }
+
+    /// Helper function to count `hidden` lines of content that were cut, within `width` columns.
+    /// Falls back to a shorter form like `…+12` when the full count is too wide.
+    fn indicator(hidden: usize, width: usize) -> String {
+        let plural = if hidden == 1 { "" } else { "s" };
+        let indicator = format!("… {} more line{}", hidden, plural);
+        if helper::display_width(&indicator) <= width {
+            return indicator;
+        }
+        helper::truncate(&format!("…+{}", hidden), width)
+    }
+
+    /// Helper function to fit the sections into `space` lines between the top and bottom lines.
+    ///
+    /// Only the first `kept` lines of content are shown, if they are limited, and any section
+    /// left with nothing to show is dropped along with the divider above it. An `indicator`
+    /// takes the place of the first hidden line. Then padding is given up, last section first.
+    fn fit_height(
+        sections: &mut Vec<Section>,
+        space: usize,
+        kept: Option<usize>,
+        indicator: Option<String>,
+    ) {
+        if let Some(kept) = kept {
+            let (index, shown) = Self::cut_point(sections, kept);
+            let drawn = shown > 0 || indicator.is_some();
+            sections.truncate(index + usize::from(drawn));
+            if drawn {
+                let section = &mut sections[index];
+                let mut lines = section.message.lines().take(shown).collect::<Vec<&str>>();
+                if let Some(indicator) = &indicator {
+                    lines.push(indicator);
+                }
+                section.message = lines.join("\n");
+            }
+        }
+
+        let mut excess = Self::section_rows(sections).saturating_sub(space);
+        for section in sections.iter_mut().rev() {
+            for padding in [&mut section.bottom_padding, &mut section.top_padding] {
+                let taken = excess.min(*padding);
+                *padding -= taken;
+                excess -= taken;
+            }
+        }
+    }
+
+    /// Helper function to find where the content is cut to show `keep` lines of it.
+    /// Returns the index of the first section with a hidden line and how many of its lines are shown.
+    fn cut_point(sections: &[Section], keep: usize) -> (usize, usize) {
+        let mut left = keep;
+        for (index, section) in sections.iter().enumerate() {
+            let lines = section.lines();
+            if lines > left {
+                return (index, left);
+            }
+            left -= lines;
+        }
+        (sections.len(), 0)
+    }
+
+    /// Helper function to count the lines between the top and bottom lines
+    /// once the content is cut to `keep` lines and the padding is given up
+    fn clipped_rows(sections: &[Section], keep: usize, indicate: bool) -> usize {
+        let (index, shown) = Self::cut_point(sections, keep);
+        let kept = &sections[..index];
+        let rows = kept.iter().map(Section::lines).sum::<usize>();
+        if shown > 0 || indicate {
+            rows + shown + usize::from(indicate) + kept.len()
+        } else {
+            rows + kept.len().saturating_sub(1)
+        }
+    }
+
+    /// Helper function to wrap the content of the box and of each section after it.
+    /// Sections keep their own formatting but wrap within the max_width of the box.
+    fn normalize_sections(&self, max_width: usize) -> Vec<Section<'_>> {
+        let border = self.lines.edge_width();
+        iter::once(self)
+            .chain(&self.sections)
+            .map(|section| {
+                let justified = section.justified_paragraphs(max_width, border);
+                let message = match &justified {
+                    Some(paragraphs) => helper::join_paragraphs(paragraphs, &Alignment::Left, 0),
+                    None => section.normalize_content(max_width, border),
+                };
+                Section {
+                    format: &section.format,
+                    message,
+                    justified,
+                    top_padding: section.format.top_padding(),
+                    bottom_padding: section.format.bottom_padding(),
+                }
             })
-            .collect::<String>()
+            .collect()
+    }
+
+    /// Helper function to wrap justified text within max_width, without spreading it out yet
+    fn justified_paragraphs(&self, max_width: usize, border: usize) -> Option<Vec<Vec<String>>> {
+        match (&self.content, &self.format.alignment) {
+            (Content::Text(text), Alignment::Justify) => {
+                let mut format = self.format.clone();
+                format.max_width = max_width;
+                Some(helper::wrap_paragraphs(text, &format, border))
+            }
+            _ => None,
+        }
+    }
+
+    /// Helper function to fit the content of the box within max_width,
+    /// less the padding and `border` columns of lines
+    fn normalize_content(&self, max_width: usize, border: usize) -> String {
+        match &self.content {
+            Content::Text(text) => {
+                let mut format = self.format.clone();
+                format.max_width = max_width;
+                helper::normalize_lines(text, &format, border)
+            }
+            Content::Box(inner) => {
+                let padding = self.format.left_padding() + self.format.right_padding();
+                let margin = inner.format.left_margin() + inner.format.right_margin();
+                let available = max_width.saturating_sub(padding + border + margin);
+                let (inner_max, inner_min) = inner.format.widths();
+                inner.render(inner_max.min(available), inner_min.min(available), 0)
+            }
+        }
+    }
+
+    /// Helper function to write a horizontal line of the box, with the pieces either side of it
+    fn write_horizontal<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        left: &str,
+        draw: impl FnOnce(&mut W) -> fmt::Result,
+        right: &str,
+    ) -> fmt::Result {
+        helper::write_whitespace(out, self.format.left_margin())?;
+        styles.write_border(out, |out| {
+            out.write_str(left)?;
+            draw(out)?;
+            out.write_str(right)
+        })?;
+        helper::write_whitespace(out, self.format.right_margin())
+    }
+
+    /// Helper function to write the line between two sections
+    fn write_divider<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        length: usize,
+    ) -> fmt::Result {
+        let lines = &self.lines;
+        self.write_horizontal(
+            out,
+            styles,
+            &lines.left_tee,
+            |out| helper::write_repeated(out, &lines.horizontal, length),
+            &lines.right_tee,
+        )?;
+        out.write_char('\n')
+    }
+
+    /// Helper function to write the top of the box
+    fn write_top<W: fmt::Write>(&self, out: &mut W, styles: &Styles, length: usize) -> fmt::Result {
+        let lines = &self.lines;
+        self.write_horizontal(
+            out,
+            styles,
+            &lines.top_left,
+            |out| self.title.write(out, &lines.top, length),
+            &lines.top_right,
+        )?;
+        out.write_char('\n')
+    }
+
+    /// Helper function to write the bottom of the box
+    fn write_bottom<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        length: usize,
+    ) -> fmt::Result {
+        let lines = &self.lines;
+        self.write_horizontal(
+            out,
+            styles,
+            &lines.bottom_left,
+            |out| self.caption.write(out, &lines.bottom, length),
+            &lines.bottom_right,
+        )
+    }
+
+    /// Write the message with the box on it's left and right
+    fn write_lines<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        format: &Formatting,
+        message: &str,
+        max_length: usize,
+    ) -> fmt::Result {
+        for line in message.lines() {
+            let line_width = helper::display_width(line);
+            let left_padding = self.left_space(format, line_width, max_length);
+            let right_padding = self.right_space(format, line_width, max_length);
+            self.write_row(out, styles, left_padding, line, right_padding)?;
+        }
+        Ok(())
+    }
+
+    /// Helper function to write the left and right lines either side of a row of content
+    fn write_row<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        left_padding: usize,
+        line: &str,
+        right_padding: usize,
+    ) -> fmt::Result {
+        helper::write_whitespace(out, self.format.left_margin())?;
+        if !self.lines.left.is_empty() {
+            styles.write_border(out, |out| out.write_str(&self.lines.left))?;
+        }
+        if left_padding + line.len() + right_padding > 0 {
+            styles.write_text(out, |out| {
+                helper::write_whitespace(out, left_padding)?;
+                styles.write_message(out, line)?;
+                helper::write_whitespace(out, right_padding)
+            })?;
+        }
+        if !self.lines.right.is_empty() {
+            styles.write_border(out, |out| out.write_str(&self.lines.right))?;
+        }
+        helper::write_whitespace(out, self.format.right_margin())?;
+        out.write_char('\n')
+    }
+
+    /// Columns of space left of a line of content
+    fn left_space(&self, format: &Formatting, line_length: usize, max_length: usize) -> usize {
+        let padding = format.left_padding();
+        match format.alignment {
+            Alignment::Left | Alignment::Justify => padding,
+            Alignment::Right => padding + max_length - line_length,
+            Alignment::Center => padding + (max_length - line_length) / 2,
+        }
+    }
+
+    /// Columns of space right of a line of content
+    fn right_space(&self, format: &Formatting, line_length: usize, max_length: usize) -> usize {
+        let padding = format.right_padding();
+        match format.alignment {
+            Alignment::Right => padding,
+            Alignment::Left | Alignment::Justify => padding + max_length - line_length,
+            Alignment::Center => padding + (max_length - line_length).div_ceil(2),
+        }
+    }
+
+    /// Helper function to write rows with nothing between the lines
+    fn write_blank_rows<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        count: usize,
+        length: usize,
+    ) -> fmt::Result {
+        for _ in 0..count {
+            self.write_row(out, styles, length, "", 0)?;
+        }
+        Ok(())
     }
 }
 
 /// Implement fmt for BoxBuilder so we can use pass a BoxBuilder to `println!` for printing
 impl fmt::Display for BoxBuilder {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_fmt(format_args!("{}", self.to_string()))
+        self.render_to(formatter)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_basic_box() {
@@ -310,67 +1052,1530 @@ mod tests {
     }
 
     #[test]
-    fn test_fmt() {
+    fn test_center_align() {
         let expected = "┌──────────────────────────────────────────────────────────────────────┐\n\
                         │                                                                      │\n\
-                        │  Lorem ipsum dolor sit amet,                                         │\n\
-                        │  consectetur adipiscing elit,                                        │\n\
+                        │                     Lorem ipsum dolor sit amet,                      │\n\
+                        │                     consectetur adipiscing elit,                     │\n\
                         │  sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.  │\n\
                         │                                                                      │\n\
                         └──────────────────────────────────────────────────────────────────────┘";
         let message = "Lorem ipsum dolor sit amet,\nconsectetur adipiscing elit,\nsed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
-        let boxed_content = BoxBuilder::new(String::from(message)).alignment(Alignment::Left);
-        assert_eq!(expected, format!("{}", boxed_content));
+        let boxed_content = BoxBuilder::new(String::from(message)).alignment(Alignment::Center);
+        assert_eq!(expected, boxed_content.to_string());
     }
 
     #[test]
-    fn empty_box() {
-        let expected = "┌────┐\n\
-                        │    │\n\
-                        │    │\n\
-                        └────┘";
-        let message = "";
-        let boxed_content = BoxBuilder::new(String::from(message));
+    fn test_center_align_asymmetric_padding() {
+        let expected = "┌──────────────┐\n\
+                        │              │\n\
+                        │     abcd     │\n\
+                        │      ab      │\n\
+                        │              │\n\
+                        └──────────────┘";
+        let boxed_content = BoxBuilder::from("abcd\nab")
+            .alignment(Alignment::Center)
+            .padding_left(5)
+            .padding_right(5)
+            .padding_top(1)
+            .padding_bottom(1);
+        assert_eq!(expected, boxed_content.to_string());
+
+        let expected = "┌─────────┐\n\
+                        │         │\n\
+                        │    abcd │\n\
+                        │     ab  │\n\
+                        │         │\n\
+                        └─────────┘";
+        let boxed_content = BoxBuilder::from("abcd\nab")
+            .alignment(Alignment::Center)
+            .padding_left(4)
+            .padding_right(1);
         assert_eq!(expected, boxed_content.to_string());
     }
 
     #[test]
-    fn line_wrapping() {
-        let expected =
-            "┌──────────────────────────────────────────────────────────────────────────────┐\n\
-             │                                                                              │\n\
-             │  Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod te  │\n\
-             │  mpor incididunt ut labore et dolore magna aliqua.                           │\n\
-             │                                                                              │\n\
-             └──────────────────────────────────────────────────────────────────────────────┘";
-        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
-        let boxed_content = BoxBuilder::new(String::from(message));
+    fn test_justify_section_to_box_width() {
+        let expected = "┌────────────────────────┐\n\
+                        │A much wider header line│\n\
+                        ├────────────────────────┤\n\
+                        │aa  bb cc dd ee ff gg hh│\n\
+                        │ii jj                   │\n\
+                        └────────────────────────┘";
+        let section = BoxBuilder::from("aa bb cc dd ee ff gg hh ii jj")
+            .padding(0)
+            .alignment(Alignment::Justify);
+        let boxed_content = BoxBuilder::from("A much wider header line")
+            .padding(0)
+            .max_width(26)
+            .section(section);
         assert_eq!(expected, boxed_content.to_string());
     }
 
     #[test]
-    fn handle_tab_character() {
-        let expected = "┌──────┐\n\
-                        │      │\n\
-                        │      │\n\
-                        │      │\n\
-                        └──────┘";
-        let message = "		";
-        let boxed_content = BoxBuilder::new(String::from(message));
+    fn test_justify_align() {
+        let expected = "┌────────────────────────┐\n\
+                        │                        │\n\
+                        │  Lorem ipsum dolor sit │\n\
+                        │  amet,     consectetur │\n\
+                        │  adipiscing elit.      │\n\
+                        │                        │\n\
+                        └────────────────────────┘";
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+        let boxed_content = BoxBuilder::from(message)
+            .alignment(Alignment::Justify)
+            .max_width(28)
+            .padding_right(1);
         assert_eq!(expected, boxed_content.to_string());
     }
 
     #[test]
-    fn test_from() {
-        let expected = "┌──────────────────────────────────────────────────────────────────────┐\n\
-                        │                                                                      │\n\
-                        │  Lorem ipsum dolor sit amet,                                         │\n\
-                        │  consectetur adipiscing elit,                                        │\n\
-                        │  sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.  │\n\
+    fn test_title() {
+        let expected = "┌─ Title ────┐\n\
+                        │            │\n\
+                        │  whatever  │\n\
+                        │            │\n\
+                        └────────────┘";
+        let boxed_content = BoxBuilder::from("whatever").title("Title");
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_title_position() {
+        let expected = "┌── Title ───┐\n\
+                        │            │\n\
+                        │  whatever  │\n\
+                        │            │\n\
+                        └────────────┘";
+        let boxed_content = BoxBuilder::from("whatever")
+            .title("Title")
+            .title_position(Position::Center);
+        assert_eq!(expected, boxed_content.to_string());
+
+        let expected = "┌──── Title ─┐\n\
+                        │            │\n\
+                        │  whatever  │\n\
+                        │            │\n\
+                        └────────────┘";
+        let boxed_content = BoxBuilder::from("whatever")
+            .title_position(Position::Right)
+            .title("Title");
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_title_decoration() {
+        let expected = "┌─┤ Title ├──┐\n\
+                        │            │\n\
+                        │  whatever  │\n\
+                        │            │\n\
+                        └────────────┘";
+        let boxed_content = BoxBuilder::from("whatever")
+            .title("Title")
+            .title_decoration("┤ ", " ├");
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_title_widens_box() {
+        let expected = "┌─ Build Summary ─┐\n\
+                        │                 │\n\
+                        │  ok             │\n\
+                        │                 │\n\
+                        └─────────────────┘";
+        let boxed_content = BoxBuilder::from("ok").title("Build Summary");
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_title_truncated_at_max_width() {
+        let expected = "┌─ Build Su… ─┐\n\
+                        │             │\n\
+                        │  ok         │\n\
+                        │             │\n\
+                        └─────────────┘";
+        let boxed_content = BoxBuilder::from("ok").title("Build Summary").max_width(15);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_caption() {
+        let expected = "┌─────────────────┐\n\
+                        │                 │\n\
+                        │  whatever       │\n\
+                        │                 │\n\
+                        └─ 2/5 ─ q: quit ─┘";
+        let boxed_content = BoxBuilder::from("whatever")
+            .caption_left("2/5")
+            .caption_right("q: quit");
+        assert_eq!(expected, boxed_content.to_string());
+
+        let expected = "┌─────────────────┐\n\
+                        │                 │\n\
+                        │  whatever       │\n\
+                        │                 │\n\
+                        └─[2/5]─[q: quit]─┘";
+        let boxed_content = boxed_content.caption_decoration("[", "]");
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_caption_slots() {
+        let expected = "┌─────────────────────┐\n\
+                        │                     │\n\
+                        │  whatever           │\n\
+                        │                     │\n\
+                        └─ a ───── b ───── c ─┘";
+        let boxed_content = BoxBuilder::from("whatever")
+            .caption_center("b")
+            .caption_right("c")
+            .caption_left("a")
+            .padding_right(11);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_caption_with_title() {
+        let expected = "┌─ Status ───┐\n\
+                        │            │\n\
+                        │  ok        │\n\
+                        │            │\n\
+                        └─ 12:00:01 ─┘";
+        let boxed_content = BoxBuilder::from("ok")
+            .title("Status")
+            .caption_center("12:00:01");
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_caption_truncated_at_max_width() {
+        let expected = "┌─────────┐\n\
+                        │         │\n\
+                        │  ok     │\n\
+                        │         │\n\
+                        └─ pres… ─┘";
+        let boxed_content = BoxBuilder::from("ok")
+            .caption_left("press q to quit")
+            .max_width(11);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_zero_width_title_and_captions() {
+        let expected = "┌──┐\n\
+                        │ok│\n\
+                        └──┘";
+        for label in ["\u{301}", "\u{200B}", "\x1B[31m\x1B[0m"] {
+            let boxed = || BoxBuilder::from("ok").padding(0).max_width(4);
+            assert_eq!(expected, boxed().title(label).to_string());
+            assert_eq!(expected, boxed().caption_left(label).to_string());
+            assert_eq!(expected, boxed().caption_center(label).to_string());
+            assert_eq!(expected, boxed().caption_right(label).to_string());
+        }
+    }
+
+    #[test]
+    fn test_border_color() {
+        let red = "\x1B[31m";
+        let reset = "\x1B[0m";
+        let expected = format!(
+            "{red}┌────┐{reset}\n\
+             {red}│{reset}    {red}│{reset}\n\
+             {red}│{reset}  ok{red}│{reset}\n\
+             {red}└────┘{reset}",
+            red = red,
+            reset = reset
+        );
+        let boxed_content = BoxBuilder::from("ok")
+            .color(AnsiColorCode::Red)
+            .padding(2)
+            .padding_right(0)
+            .padding_bottom(0);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_text_color() {
+        let expected = "┌──┐\n\
+                        │\x1B[38;2;1;2;3mok\x1B[0m│\n\
+                        └──┘";
+        let boxed_content = BoxBuilder::from("ok").text_color_rgb(1, 2, 3).padding(0);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_background_color() {
+        let border = "\x1B[32;48;5;236m";
+        let text = "\x1B[97;48;5;236m";
+        let reset = "\x1B[0m";
+        let expected = format!(
+            "{border}┌────┐{reset}\n\
+             {border}│{reset}{text}    {reset}{border}│{reset}\n\
+             {border}│{reset}{text} ok {reset}{border}│{reset}\n\
+             {border}└────┘{reset}",
+            border = border,
+            text = text,
+            reset = reset
+        );
+        let boxed_content = BoxBuilder::from("ok")
+            .color(AnsiColorCode::Green)
+            .text_color(AnsiColorCode::BrightWhite)
+            .background_color_8(236)
+            .padding(1)
+            .padding_top(1);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_attributes() {
+        let border = "\x1B[1;34m";
+        let text = "\x1B[4;9;33m";
+        let reset = "\x1B[0m";
+        let expected = format!(
+            "{border}┌──┐{reset}\n\
+             {border}│{reset}{text}ok{reset}{border}│{reset}\n\
+             {border}└──┘{reset}",
+            border = border,
+            text = text,
+            reset = reset
+        );
+        let boxed_content = BoxBuilder::from("ok")
+            .padding(0)
+            .color(AnsiColorCode::Blue)
+            .attribute(Attribute::Bold)
+            .text_color(AnsiColorCode::Yellow)
+            .text_attribute(Attribute::Underline)
+            .text_attribute(Attribute::Strikethrough)
+            .text_attribute(Attribute::Underline);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_attributes_reset() {
+        let boxed_content = BoxBuilder::from("whatever\nwhatever")
+            .attribute(Attribute::Reverse)
+            .text_attribute(Attribute::Blink)
+            .text_attribute(Attribute::Dim);
+        let rendered = boxed_content.to_string();
+        assert!(rendered.ends_with("\x1B[0m"));
+        for line in rendered.lines() {
+            assert!(line.ends_with("\x1B[0m"));
+            assert_eq!(
+                line.matches("\x1B[").count(),
+                2 * line.matches("\x1B[0m").count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rounded_lines() {
+        let expected = "╭────────────╮\n\
+                        │            │\n\
+                        │  whatever  │\n\
+                        │            │\n\
+                        ╰────────────╯";
+        let boxed_content = BoxBuilder::from("whatever").line_type(LineType::Rounded);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_ascii_line_type() {
+        let expected = "+------------+\n\
+                        |            |\n\
+                        |  whatever  |\n\
+                        |            |\n\
+                        +------------+";
+        let boxed_content = BoxBuilder::from("whatever").line_type(LineType::Ascii);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_heavy_dotted_lines() {
+        let expected = "┏┅┅┅┅┅┅┅┅┅┅┅┅┓\n\
+                        ┇            ┇\n\
+                        ┇  whatever  ┇\n\
+                        ┇            ┇\n\
+                        ┗┅┅┅┅┅┅┅┅┅┅┅┅┛";
+        let boxed_content = BoxBuilder::from("whatever").line_type(LineType::HeavyDotted);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_double_dash_lines() {
+        let expected = "┏╍╍╍╍╍╍╍╍╍╍╍╍┓\n\
+                        ╏            ╏\n\
+                        ╏  whatever  ╏\n\
+                        ╏            ╏\n\
+                        ┗╍╍╍╍╍╍╍╍╍╍╍╍┛";
+        let boxed_content = BoxBuilder::from("whatever").line_type(LineType::DoubleDash);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_block_line_type() {
+        let expected = "██████████████\n\
+                        █            █\n\
+                        █  whatever  █\n\
+                        █            █\n\
+                        ██████████████";
+        let boxed_content = BoxBuilder::from("whatever").line_type(LineType::Block);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_half_block_lines() {
+        let expected = "▛▀▀▀▀▀▀▀▀▀▀▀▀▜\n\
+                        ▌            ▐\n\
+                        ▌  whatever  ▐\n\
+                        ▌            ▐\n\
+                        ▙▄▄▄▄▄▄▄▄▄▄▄▄▟";
+        let boxed_content = BoxBuilder::from("whatever").line_type(LineType::HalfBlock);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_half_block_dividers() {
+        let expected = "▛▀▀▀▀▀▀▀▀▀▀▀▀▜\n\
+                        ▌            ▐\n\
+                        ▌  whatever  ▐\n\
+                        ▌            ▐\n\
+                        ▛▀▀▀▀▀▀▀▀▀▀▀▀▜\n\
+                        ▌            ▐\n\
+                        ▌  what      ▐\n\
+                        ▌            ▐\n\
+                        ▙▄▄▄▄▄▄▄▄▄▄▄▄▟";
+        let boxed_content = BoxBuilder::from("whatever")
+            .section(BoxBuilder::from("what"))
+            .line_type(LineType::HalfBlock);
+        assert_eq!(expected, boxed_content.to_string());
+
+        let expected = "▛▀▀▀▛▀▀▀▜\n\
+                        ▌ a ▌ b ▐\n\
+                        ▛▀▀▀▛▀▀▀▜\n\
+                        ▌ c ▌ d ▐\n\
+                        ▙▄▄▄▙▄▄▄▟";
+        let table = TableBuilder::new()
+            .header(&["a", "b"])
+            .row(&["c", "d"])
+            .line_type(LineType::HalfBlock);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_hidden_lines() {
+        let expected = concat!(
+            "              \n",
+            "              \n",
+            "   whatever   \n",
+            "              \n",
+            "              "
+        );
+        let boxed_content = BoxBuilder::from("whatever").line_type(LineType::Hidden);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_mixed_sides() {
+        let expected = "╒════════════╕\n\
+                        │            │\n\
+                        │  whatever  │\n\
+                        │            │\n\
+                        └────────────┘";
+        let boxed_content = BoxBuilder::from("whatever").line_type_top(LineType::Double);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_side_keeps_custom_lines() {
+        let expected = "╭────────────╮\n\
+                        │            │\n\
+                        │  whatever  │\n\
+                        │            │\n\
+                        ╘════════════╛";
+        let rounded = Lines::from(LineType::Basic).top_left("╭").top_right("╮");
+        let boxed_content = BoxBuilder::from("whatever")
+            .lines(rounded)
+            .unwrap()
+            .line_type_bottom(LineType::Double);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_side_keeps_rounded_corners() {
+        let expected = "╭╌╌╌╌╌╌╌╌╌╌╌╌╮\n\
+                        │            │\n\
+                        │  whatever  │\n\
+                        │            │\n\
+                        ╰────────────╯";
+        let boxed_content = BoxBuilder::from("whatever")
+            .line_type(LineType::Rounded)
+            .line_type_top(LineType::Dotted);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_callout_left_bar() {
+        let expected = "┎────────────┐\n\
+                        ┃            │\n\
+                        ┃  whatever  │\n\
+                        ┃            │\n\
+                        ┖────────────┘";
+        let boxed_content = BoxBuilder::from("whatever").line_type_left(LineType::Bold);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_mixed_sides_after_line_type() {
+        let expected = "┏━━━━━━━━━━━━┓\n\
+                        ┃            ┃\n\
+                        ┃  whatever  ┃\n\
+                        ┃            ┃\n\
+                        ┗╍╍╍╍╍╍╍╍╍╍╍╍┛";
+        let boxed_content = BoxBuilder::from("whatever")
+            .line_type(LineType::Bold)
+            .line_type_bottom(LineType::DoubleDash);
+        assert_eq!(expected, boxed_content.to_string());
+
+        let expected = "╓────────────╖\n\
+                        ║            ║\n\
+                        ║  whatever  ║\n\
+                        ║            ║\n\
+                        ╙────────────╜";
+        let boxed_content = BoxBuilder::from("whatever")
+            .line_type_right(LineType::Double)
+            .line_type_left(LineType::Double);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_ascii_lines() {
+        let expected = "+------------+\n\
+                        |            |\n\
+                        |  whatever  |\n\
+                        |            |\n\
+                        +------------+";
+        let ascii = Lines::from(LineType::Basic)
+            .top("-")
+            .bottom("-")
+            .left("|")
+            .right("|")
+            .top_left("+")
+            .top_right("+")
+            .bottom_left("+")
+            .bottom_right("+");
+        let boxed_content = BoxBuilder::from("whatever").lines(ascii).unwrap();
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_block_lines() {
+        let expected = "█▀▀▀▀▀▀▀▀▀▀▀▀█\n\
+                        █            █\n\
+                        █  whatever  █\n\
+                        █            █\n\
+                        █▄▄▄▄▄▄▄▄▄▄▄▄█";
+        let block = Lines::from(LineType::Basic)
+            .top("▀")
+            .bottom("▄")
+            .left("█")
+            .right("█")
+            .top_left("█")
+            .top_right("█")
+            .bottom_left("█")
+            .bottom_right("█");
+        let boxed_content = BoxBuilder::from("whatever").lines(block).unwrap();
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_multi_column_lines() {
+        let expected = "/*=-=-=-=-=-=-=*/\n\
+                        **  whatever   **\n\
+                        \\*=-=-=-=-=-=-=*/";
+        let lines = Lines::from(LineType::Basic)
+            .top("=-")
+            .bottom("=-")
+            .left("**")
+            .right("**")
+            .top_left("/*")
+            .top_right("*/")
+            .bottom_left("\\*")
+            .bottom_right("*/")
+            .left_tee("**")
+            .right_tee("**");
+        let boxed_content = BoxBuilder::from("whatever")
+            .lines(lines)
+            .unwrap()
+            .padding(2)
+            .padding_right(3)
+            .padding_top(0)
+            .padding_bottom(0);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_multi_column_lines_max_width() {
+        let lines = Lines::from(LineType::Basic)
+            .left("||")
+            .right("||")
+            .top_left("++")
+            .top_right("++")
+            .bottom_left("++")
+            .bottom_right("++")
+            .left_tee("|-")
+            .right_tee("-|");
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+        let boxed_content = BoxBuilder::from(message)
+            .lines(lines)
+            .unwrap()
+            .max_width(20);
+        let rendered = boxed_content.to_string();
+        let width = helper::display_width(rendered.lines().next().unwrap());
+        assert!(width <= 20);
+        for line in rendered.lines() {
+            assert_eq!(helper::display_width(line), width);
+        }
+    }
+
+    #[test]
+    fn test_invalid_lines() {
+        let lines = Lines::from(LineType::Basic).left("||");
+        assert!(BoxBuilder::from("whatever").lines(lines).is_err());
+    }
+
+    #[test]
+    fn test_sections() {
+        let expected = "┌────────────────┐\n\
+                        │                │\n\
+                        │  Header        │\n\
+                        │                │\n\
+                        ├────────────────┤\n\
+                        │                │\n\
+                        │  body text     │\n\
+                        │  and more body │\n\
+                        │                │\n\
+                        └────────────────┘";
+        let boxed_content = BoxBuilder::from("Header").section(
+            BoxBuilder::from("body text\nand more body")
+                .padding_right(1)
+                .padding_left(2),
+        );
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_sections_own_alignment() {
+        let expected = "╔═════════════╗\n\
+                        ║   Summary   ║\n\
+                        ╠═════════════╣\n\
+                        ║ passed   12 ║\n\
+                        ║ failed    0 ║\n\
+                        ╠═════════════╣\n\
+                        ║        done ║\n\
+                        ╚═════════════╝";
+        let boxed_content = BoxBuilder::from("Summary")
+            .line_type(LineType::Double)
+            .alignment(Alignment::Center)
+            .padding(0)
+            .section(
+                BoxBuilder::from("passed   12\nfailed    0")
+                    .padding(1)
+                    .padding_top(0),
+            )
+            .section(
+                BoxBuilder::from("done")
+                    .alignment(Alignment::Right)
+                    .padding(0)
+                    .padding_right(1),
+            );
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_sections_heavy_tee() {
+        let expected = "┏━━━┓\n\
+                        ┃ a ┃\n\
+                        ┣━━━┫\n\
+                        ┃ b ┃\n\
+                        ┗━━━┛";
+        let boxed_content = BoxBuilder::from("a")
+            .line_type(LineType::Bold)
+            .padding(1)
+            .section(BoxBuilder::from("b").padding(1));
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_sections_share_max_width() {
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+        let boxed_content = BoxBuilder::from("Header")
+            .max_width(24)
+            .section(BoxBuilder::from(message).max_width(80));
+        let rendered = boxed_content.to_string();
+        let width = helper::display_width(rendered.lines().next().unwrap());
+        assert!(width <= 24);
+        for line in rendered.lines() {
+            assert_eq!(helper::display_width(line), width);
+        }
+    }
+
+    #[test]
+    fn test_nested_box() {
+        let expected = "┌────────────────┐\n\
+                        │                │\n\
+                        │  ┌──────────┐  │\n\
+                        │  │ whatever │  │\n\
+                        │  └──────────┘  │\n\
+                        │                │\n\
+                        └────────────────┘";
+        let inner = BoxBuilder::from("whatever").padding(1).padding_top(0);
+        let boxed_content = BoxBuilder::from_box(inner);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_nested_box_alignment() {
+        let expected = "╔═══════════════╗\n\
+                        ║ outer section ║\n\
+                        ╠═══════════════╣\n\
+                        ║     ┌───┐     ║\n\
+                        ║     │ a │     ║\n\
+                        ║     └───┘     ║\n\
+                        ╚═══════════════╝";
+        let inner = BoxBuilder::from("a").padding(1).padding_top(0);
+        let boxed_content = BoxBuilder::from("outer section")
+            .line_type(LineType::Double)
+            .padding(1)
+            .padding_top(0)
+            .section(
+                BoxBuilder::from_box(inner)
+                    .alignment(Alignment::Center)
+                    .padding(1)
+                    .padding_top(0),
+            );
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_nested_colored_box() {
+        let inner = BoxBuilder::from("whatever").color(AnsiColorCode::Red);
+        let plain = BoxBuilder::from_box(BoxBuilder::from("whatever")).to_string();
+        let colored = BoxBuilder::from_box(inner).to_string();
+        assert_ne!(plain, colored);
+        assert_eq!(plain, ansi::strip_escapes(&colored));
+    }
+
+    #[test]
+    fn test_nested_box_wraps_to_outer_width() {
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+        let inner = BoxBuilder::from(message).title("Inner title is long");
+        let boxed_content = BoxBuilder::from_box(inner).max_width(30).to_string();
+        for line in boxed_content.lines() {
+            assert!(helper::display_width(line) <= 30);
+        }
+        let inner_width = helper::display_width(boxed_content.lines().nth(2).unwrap());
+        for line in boxed_content.lines() {
+            assert_eq!(helper::display_width(line), inner_width);
+        }
+        assert!(boxed_content.contains("─ Inner title is lo… ─"));
+    }
+
+    #[test]
+    fn test_colored_message() {
+        let expected = "┌─────────────┐\n\
+                        │             │\n\
+                        │  \x1B[32mLorem\x1B[0m      │\n\
+                        │  \x1B[32mipsum\x1B[0m      │\n\
+                        │  dolor sit  │\n\
+                        │  amet       │\n\
+                        │             │\n\
+                        └─────────────┘";
+        let message = "\x1B[32mLorem ipsum\x1B[0m dolor sit amet";
+        let boxed_content = BoxBuilder::from(message).max_width(16);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_colored_message_lines_up() {
+        let message = "\x1B[1;31mLorem ipsum dolor sit amet,\x1B[0m consectetur \x1B]8;;https://example.com/a-b\x1B\\adipiscing\x1B]8;;\x1B\\ elit";
+        let plain = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+        for wrapping in [Wrapping::Word, Wrapping::Hard] {
+            let colored = BoxBuilder::from(message)
+                .max_width(20)
+                .wrapping(wrapping.clone())
+                .to_string();
+            let expected = BoxBuilder::from(plain)
+                .max_width(20)
+                .wrapping(wrapping)
+                .to_string();
+            assert_eq!(expected, ansi::strip_escapes(&colored));
+        }
+    }
+
+    #[test]
+    fn test_render_to() {
+        let boxed_content = BoxBuilder::from("Lorem ipsum dolor sit amet")
+            .max_width(16)
+            .title("Title")
+            .color(AnsiColorCode::Red)
+            .text_color(AnsiColorCode::Blue)
+            .section(BoxBuilder::from("section"));
+        let mut rendered = String::new();
+        boxed_content.render_to(&mut rendered).unwrap();
+        assert_eq!(rendered, boxed_content.to_string());
+        assert_eq!(format!("{}", boxed_content), boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_write_to() {
+        let boxed_content = BoxBuilder::from("whatever");
+        let mut written = Vec::new();
+        boxed_content.write_to(&mut written).unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            boxed_content.to_string()
+        );
+    }
+
+    #[test]
+    fn test_write_to_error() {
+        struct Full;
+        impl io::Write for Full {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::WriteZero, "full"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let error = BoxBuilder::from("whatever")
+            .write_to(&mut Full)
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn test_fmt() {
+        let expected = "┌──────────────────────────────────────────────────────────────────────┐\n\
+                        │                                                                      │\n\
+                        │  Lorem ipsum dolor sit amet,                                         │\n\
+                        │  consectetur adipiscing elit,                                        │\n\
+                        │  sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.  │\n\
+                        │                                                                      │\n\
+                        └──────────────────────────────────────────────────────────────────────┘";
+        let message = "Lorem ipsum dolor sit amet,\nconsectetur adipiscing elit,\nsed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        let boxed_content = BoxBuilder::new(String::from(message)).alignment(Alignment::Left);
+        assert_eq!(expected, format!("{}", boxed_content));
+    }
+
+    #[test]
+    fn empty_box() {
+        let expected = "┌────┐\n\
+                        │    │\n\
+                        │    │\n\
+                        └────┘";
+        let message = "";
+        let boxed_content = BoxBuilder::new(String::from(message));
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn line_wrapping() {
+        let expected =
+            "┌──────────────────────────────────────────────────────────────────────────────┐\n\
+             │                                                                              │\n\
+             │  Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod te  │\n\
+             │  mpor incididunt ut labore et dolore magna aliqua.                           │\n\
+             │                                                                              │\n\
+             └──────────────────────────────────────────────────────────────────────────────┘";
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        let boxed_content = BoxBuilder::new(String::from(message)).wrapping(Wrapping::Hard);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn word_wrapping() {
+        let expected =
+            "┌───────────────────────────────────────────────────────────────────────────┐\n\
+             │                                                                           │\n\
+             │  Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod  │\n\
+             │  tempor incididunt ut labore et dolore magna aliqua.                      │\n\
+             │                                                                           │\n\
+             └───────────────────────────────────────────────────────────────────────────┘";
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        let boxed_content = BoxBuilder::new(String::from(message));
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn handle_tab_character() {
+        let expected = "┌────────────────────┐\n\
+                        │                    │\n\
+                        │                    │\n\
+                        │                    │\n\
+                        └────────────────────┘";
+        let message = "		";
+        let boxed_content = BoxBuilder::new(String::from(message));
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_tab_stops() {
+        let expected = "┌────────────────────────┐\n\
+                        │                        │\n\
+                        │  name    size          │\n\
+                        │  a.txt   12            │\n\
+                        │  data.bin        4096  │\n\
+                        │                        │\n\
+                        └────────────────────────┘";
+        let message = "name\tsize\na.txt\t12\ndata.bin\t4096";
+        let boxed_content = BoxBuilder::from(message);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_tab_width() {
+        let expected = "┌─────────────────┐\n\
+                        │                 │\n\
+                        │  a   b          │\n\
+                        │  abcd        c  │\n\
+                        │                 │\n\
+                        └─────────────────┘";
+        let message = "a\tb\nabcd\t\tc";
+        let boxed_content = BoxBuilder::from(message).tab_width(4);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    /// Draw a box as it is drawn in a terminal `columns` wide
+    fn render_in_terminal(boxed_content: &BoxBuilder, columns: usize) -> String {
+        let (max_width, min_width) = boxed_content.format.widths_in(columns);
+        boxed_content.render(max_width, min_width, 0)
+    }
+
+    #[test]
+    fn test_fit_terminal() {
+        let fill = "┌────────────────────┐\n\
+                    │                    │\n\
+                    │  Lorem             │\n\
+                    │                    │\n\
+                    └────────────────────┘";
+        let shrink = "┌─────────┐\n\
+                      │         │\n\
+                      │  Lorem  │\n\
+                      │         │\n\
+                      └─────────┘";
+        let wrapped = "┌──────────────────┐\n\
+                       │                  │\n\
+                       │  Lorem ipsum     │\n\
+                       │  dolor sit amet  │\n\
+                       │                  │\n\
+                       └──────────────────┘";
+        let lorem = || BoxBuilder::from("Lorem").max_width(10);
+        let filled = lorem().fit_terminal(TerminalFit::Fill);
+        assert_eq!(fill, render_in_terminal(&filled, 22));
+        let shrunk = lorem().fit_terminal(TerminalFit::Shrink);
+        assert_eq!(shrink, render_in_terminal(&shrunk, 22));
+        let boxed_content =
+            BoxBuilder::from("Lorem ipsum dolor sit amet").fit_terminal(TerminalFit::Shrink);
+        assert_eq!(wrapped, render_in_terminal(&boxed_content, 22));
+        let indented = BoxBuilder::from("Lorem")
+            .margin_left(2)
+            .fit_terminal(TerminalFit::Fill);
+        for line in render_in_terminal(&indented, 22).lines() {
+            assert_eq!(helper::display_width(line), 22);
+        }
+        let exact = lorem().width(12).fit_terminal(TerminalFit::Fill);
+        assert_eq!(
+            render_in_terminal(&exact, 22).lines().next(),
+            Some("┌──────────┐")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fit_terminal_of() {
+        let output = std::fs::File::open("Cargo.toml").unwrap();
+        let boxed_content = BoxBuilder::from("Lorem").fit_terminal_of(TerminalFit::Fill, &output);
+        assert_eq!(boxed_content.format.terminal_fd, output.as_raw_fd());
+    }
+
+    #[test]
+    fn test_width() {
+        let expected = "┌──────────┐\n\
+                        │          │\n\
+                        │  Lorem   │\n\
+                        │          │\n\
+                        └──────────┘";
+        let boxed_content = BoxBuilder::from("Lorem").width(12);
+        assert_eq!(expected, boxed_content.to_string());
+        let wrapped = BoxBuilder::from("Lorem ipsum dolor").width(12).to_string();
+        assert_eq!(wrapped.lines().count(), 7);
+        for line in wrapped.lines() {
+            assert_eq!(helper::display_width(line), 12);
+        }
+    }
+
+    #[test]
+    fn test_min_width() {
+        let expected = "┌──────────┐\n\
+                        │Lorem     │\n\
+                        └──────────┘";
+        let boxed_content = BoxBuilder::from("Lorem").padding(0).min_width(12);
+        assert_eq!(expected, boxed_content.to_string());
+        let wide = BoxBuilder::from("Lorem ipsum").padding(0).min_width(5);
+        assert_eq!(
+            wide.to_string(),
+            BoxBuilder::from("Lorem ipsum").padding(0).to_string()
+        );
+    }
+
+    #[test]
+    fn test_min_height() {
+        let expected = "┌─┐\n\
+                        │a│\n\
+                        │ │\n\
+                        │ │\n\
+                        └─┘";
+        let boxed_content = BoxBuilder::from("a").padding(0).min_height(5);
+        assert_eq!(expected, boxed_content.to_string());
+        assert_eq!(
+            BoxBuilder::from("a\nb\nc\nd")
+                .padding(0)
+                .min_height(2)
+                .to_string(),
+            BoxBuilder::from("a\nb\nc\nd").padding(0).to_string()
+        );
+    }
+
+    #[test]
+    fn test_height_indicator() {
+        let expected = "┌──────────────┐\n\
+                        │a             │\n\
+                        │b             │\n\
+                        │… 3 more lines│\n\
+                        └──────────────┘";
+        let boxed_content = BoxBuilder::from("a\nb\nc\nd\ne")
+            .padding(0)
+            .width(16)
+            .height(5);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_height_clip() {
+        let expected = "┌─┐\n\
+                        │a│\n\
+                        │b│\n\
+                        │c│\n\
+                        │d│\n\
+                        └─┘";
+        let boxed_content = BoxBuilder::from("a\nb\nc\nd\ne")
+            .padding(0)
+            .padding_top(1)
+            .padding_bottom(1)
+            .height(6)
+            .vertical_overflow(VerticalOverflow::Clip);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_height_clips_sections() {
+        let expected = "┌──────────────┐\n\
+                        │a             │\n\
+                        │… 3 more lines│\n\
+                        │              │\n\
+                        └──────────────┘";
+        let boxed_content = BoxBuilder::from("a\nb")
+            .padding(0)
+            .section(BoxBuilder::from("c\nd").padding(0))
+            .width(16)
+            .height(5);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_height_keeps_section_with_content() {
+        let expected = "┌─┐\n\
+                        │a│\n\
+                        ├─┤\n\
+                        │c│\n\
+                        └─┘";
+        let boxed_content = BoxBuilder::from("a")
+            .padding(0)
+            .section(BoxBuilder::from("c\nd").padding(0))
+            .section(BoxBuilder::from("e").padding(0))
+            .height(5)
+            .vertical_overflow(VerticalOverflow::Clip);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_height_takes_padding() {
+        let expected = "┌──────────────────┐\n\
+                        │  … 3 more lines  │\n\
+                        └──────────────────┘";
+        let boxed_content = |height| {
+            BoxBuilder::from("a\nb\nc")
+                .width(20)
+                .height(height)
+                .to_string()
+        };
+        assert_eq!(expected, boxed_content(3));
+        assert_eq!(expected, boxed_content(1));
+        let unpadded = "┌──────────────────┐\n\
+                        │  a               │\n\
+                        │  b               │\n\
+                        │  c               │\n\
+                        └──────────────────┘";
+        assert_eq!(unpadded, boxed_content(5));
+    }
+
+    #[test]
+    fn test_height_takes_padding_before_content() {
+        let expected = "┌────────────────────┐\n\
+                        │  a                 │\n\
+                        │  b                 │\n\
+                        │  … 3 more lines    │\n\
+                        └────────────────────┘";
+        let boxed_content = BoxBuilder::from("a\nb\nc\nd\ne").min_width(22).height(5);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_height_indicator_widens_box() {
+        let expected = "┌──────────────┐\n\
+                        │… 2 more lines│\n\
+                        └──────────────┘";
+        let boxed_content = BoxBuilder::from("a\nb").padding(0).height(3);
+        assert_eq!(expected, boxed_content.to_string());
+
+        let expected = "┌───┐\n\
+                        │…+2│\n\
+                        └───┘";
+        let boxed_content = BoxBuilder::from("a\nb").padding(0).max_width(5).height(3);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_height_clip_minimum() {
+        let boxed_content = BoxBuilder::from("a\nb\nc")
+            .height(1)
+            .vertical_overflow(VerticalOverflow::Clip);
+        assert_eq!(boxed_content.to_string(), "┌─────┐\n└─────┘");
+    }
+
+    #[test]
+    fn test_width_precedence() {
+        let narrowest = BoxBuilder::from("hello").width(7).to_string();
+        assert_eq!(narrowest.lines().next(), Some("┌─────┐"));
+        assert_eq!(BoxBuilder::from("hello").width(1).to_string(), narrowest);
+        assert_eq!(BoxBuilder::from("hello").width(0).to_string(), narrowest);
+        let capped = BoxBuilder::from("hello")
+            .padding(0)
+            .max_width(10)
+            .min_width(30);
+        assert_eq!(capped.to_string(), "┌────────┐\n│hello   │\n└────────┘");
+    }
+
+    #[test]
+    fn test_height_stretches_short_content() {
+        let boxed_content = BoxBuilder::from("a").padding(0).height(4);
+        assert_eq!(boxed_content.to_string(), "┌─┐\n│a│\n│ │\n└─┘");
+    }
+
+    #[test]
+    fn test_vertical_alignment() {
+        let middle = "┌─┐\n\
+                      │ │\n\
+                      │ │\n\
+                      │a│\n\
+                      │ │\n\
+                      │ │\n\
+                      │ │\n\
+                      └─┘";
+        let bottom = "┌─┐\n\
+                      │ │\n\
+                      │ │\n\
+                      │ │\n\
+                      │ │\n\
+                      │a│\n\
+                      │ │\n\
+                      └─┘";
+        let boxed_content = |alignment| {
+            BoxBuilder::from("a")
+                .padding(0)
+                .padding_top(1)
+                .padding_bottom(1)
+                .height(8)
+                .vertical_alignment(alignment)
+                .to_string()
+        };
+        assert_eq!(middle, boxed_content(VerticalAlignment::Middle));
+        assert_eq!(bottom, boxed_content(VerticalAlignment::Bottom));
+    }
+
+    #[test]
+    fn test_vertical_alignment_with_sections() {
+        let expected = "┌─┐\n\
+                        │a│\n\
+                        ├─┤\n\
+                        │ │\n\
+                        │b│\n\
+                        └─┘";
+        let boxed_content = BoxBuilder::from("a")
+            .padding(0)
+            .section(BoxBuilder::from("b").padding(0))
+            .min_height(6)
+            .vertical_alignment(VerticalAlignment::Bottom);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_overflow_truncate() {
+        let expected = "┌─────────────────┐\n\
+                        │/very/lo…/file.rs│\n\
+                        │short            │\n\
+                        └─────────────────┘";
+        let boxed_content = BoxBuilder::from("/very/long/path/to/file.rs\nshort")
+            .padding(0)
+            .max_width(19)
+            .overflow(Overflow::TruncateMiddle);
+        assert_eq!(expected, boxed_content.to_string());
+        let start = BoxBuilder::from("/very/long/path/to/file.rs")
+            .padding(0)
+            .max_width(14)
+            .overflow(Overflow::TruncateStart)
+            .ellipsis("...");
+        assert_eq!(
+            start.to_string(),
+            "┌────────────┐\n│...o/file.rs│\n└────────────┘"
+        );
+    }
+
+    #[test]
+    fn test_margin() {
+        let expected = "\x20      \n\
+                        \x20 ┌─┐  \n\
+                        \x20 │a│  \n\
+                        \x20 └─┘  \n\
+                        \x20      ";
+        let boxed_content = BoxBuilder::from("a").padding(0).margin(2);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_nested_margin() {
+        let expected = "┌─────┐\n\
+                        │     │\n\
+                        │┌─┐  │\n\
+                        ││a│  │\n\
+                        │└─┘  │\n\
+                        │     │\n\
+                        └─────┘";
+        let inner = BoxBuilder::from("a")
+            .padding(0)
+            .margin_top(1)
+            .margin_bottom(1)
+            .margin_right(2);
+        let boxed_content = BoxBuilder::from_box(inner).padding(0);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_margin_left_uncolored() {
+        let boxed_content = BoxBuilder::from("a")
+            .padding(0)
+            .margin_left(3)
+            .color(AnsiColorCode::Red)
+            .text_color(AnsiColorCode::Blue);
+        let rendered = boxed_content.to_string();
+        for line in rendered.lines() {
+            assert!(line.starts_with("   \x1B["));
+        }
+        assert_eq!(ansi::strip_escapes(&rendered), "   ┌─┐\n   │a│\n   └─┘");
+    }
+
+    #[test]
+    fn test_margin_within_max_width() {
+        let message = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+        let boxed_content = BoxBuilder::from(message)
+            .max_width(30)
+            .margin_left(4)
+            .margin_right(2);
+        let rendered = boxed_content.to_string();
+        let width = helper::display_width(rendered.lines().next().unwrap());
+        assert!(width <= 30);
+        for line in rendered.lines() {
+            assert_eq!(helper::display_width(line), width);
+            let trimmed = line.trim_start_matches(' ').trim_end_matches(' ');
+            assert_eq!(&line[..4], "    ");
+            assert_eq!(helper::display_width(trimmed) + 6, width);
+        }
+        let exact = BoxBuilder::from("a").width(10).margin_left(4).to_string();
+        assert_eq!(helper::display_width(exact.lines().next().unwrap()), 14);
+    }
+
+    #[test]
+    fn test_from() {
+        let expected = "┌──────────────────────────────────────────────────────────────────────┐\n\
+                        │                                                                      │\n\
+                        │  Lorem ipsum dolor sit amet,                                         │\n\
+                        │  consectetur adipiscing elit,                                        │\n\
+                        │  sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.  │\n\
                         │                                                                      │\n\
                         └──────────────────────────────────────────────────────────────────────┘";
         let message = "Lorem ipsum dolor sit amet,\nconsectetur adipiscing elit,\nsed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
         let boxed_content = BoxBuilder::from(message).alignment(Alignment::Left);
         assert_eq!(expected, format!("{}", boxed_content));
     }
+
+    #[test]
+    fn test_wide_characters() {
+        let expected = "┌──────────┐\n\
+                        │          │\n\
+                        │  日本語  │\n\
+                        │  abc     │\n\
+                        │          │\n\
+                        └──────────┘";
+        let boxed_content = BoxBuilder::from("日本語\nabc");
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_accented_characters() {
+        let expected = "┌────────────┐\n\
+                        │            │\n\
+                        │  déjà vu   │\n\
+                        │  whatever  │\n\
+                        │            │\n\
+                        └────────────┘";
+        let boxed_content = BoxBuilder::from("déjà vu\nwhatever");
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    #[test]
+    fn test_emoji_and_joiners() {
+        let expected = "┌─────────┐\n\
+                        │         │\n\
+                        │  ✅ ok  │\n\
+                        │  👨\u{200d}👩\u{200d}👧     │\n\
+                        │  e\u{301}      │\n\
+                        │         │\n\
+                        └─────────┘";
+        let boxed_content = BoxBuilder::from("✅ ok\n👨\u{200d}👩\u{200d}👧\ne\u{301}");
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    /// Every combination of global and per side padding for each alignment
+    fn padded_boxes(message: &str) -> Vec<(usize, usize, BoxBuilder)> {
+        let mut boxes = Vec::new();
+        for padding in 0..4 {
+            for left in (0..4).map(Some).chain(Some(None)) {
+                for right in (0..4).map(Some).chain(Some(None)) {
+                    for alignment in 0..4 {
+                        let alignment = match alignment {
+                            0 => Alignment::Left,
+                            1 => Alignment::Right,
+                            2 => Alignment::Center,
+                            _ => Alignment::Justify,
+                        };
+                        let mut boxed_content = BoxBuilder::from(message)
+                            .padding(padding)
+                            .alignment(alignment)
+                            .max_width(30);
+                        if let Some(left) = left {
+                            boxed_content = boxed_content.padding_left(left);
+                        }
+                        if let Some(right) = right {
+                            boxed_content = boxed_content.padding_right(right);
+                        }
+                        boxes.push((
+                            left.unwrap_or(padding),
+                            right.unwrap_or(padding),
+                            boxed_content,
+                        ));
+                    }
+                }
+            }
+        }
+        boxes
+    }
+
+    #[test]
+    fn test_padding_combinations_same_width() {
+        let messages = [
+            "",
+            "whatever",
+            "whatever\nwhatever and more",
+            "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor",
+            "日本語\ncafé\n✅ ok",
+        ];
+        for message in messages.iter() {
+            for (left, right, boxed_content) in padded_boxes(message) {
+                let rendered = boxed_content.to_string();
+                let top = rendered.lines().next().unwrap();
+                let width = helper::display_width(top);
+                let content_width = helper::max_line_length(&helper::normalize_lines(
+                    message,
+                    &boxed_content.format,
+                    2,
+                ));
+                assert_eq!(width, content_width + left + right + 2, "{}", rendered);
+                for line in rendered.lines() {
+                    assert_eq!(helper::display_width(line), width, "{}", rendered);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_padding_combinations_left_aligned_content() {
+        for (left, right, boxed_content) in padded_boxes("whatever\nwhat") {
+            if let Alignment::Left = boxed_content.format.alignment {
+                let rendered = boxed_content.to_string();
+                for line in rendered.lines().filter(|line| line.contains('w')) {
+                    let expected_start = format!("│{}w", helper::gen_whitespace(left));
+                    assert!(line.starts_with(&expected_start), "{}", rendered);
+                }
+                let widest = format!("whatever{}│", helper::gen_whitespace(right));
+                assert!(rendered.contains(&widest), "{}", rendered);
+            }
+        }
+    }
+
+    #[test]
+    fn test_padding_combinations_right_aligned_content() {
+        for (left, right, boxed_content) in padded_boxes("whatever\nwhat") {
+            if let Alignment::Right = boxed_content.format.alignment {
+                let rendered = boxed_content.to_string();
+                let widest = format!(
+                    "│{}whatever{}│",
+                    helper::gen_whitespace(left),
+                    helper::gen_whitespace(right)
+                );
+                assert!(rendered.contains(&widest), "{}", rendered);
+                let narrow = format!(
+                    "│{}what{}│",
+                    helper::gen_whitespace(left + 4),
+                    helper::gen_whitespace(right)
+                );
+                assert!(rendered.contains(&narrow), "{}", rendered);
+            }
+        }
+    }
+
+    #[test]
+    fn test_padding_combinations_center_aligned_content() {
+        for (left, right, boxed_content) in padded_boxes("whatever\nwh") {
+            if let Alignment::Center = boxed_content.format.alignment {
+                let rendered = boxed_content.to_string();
+                let widest = format!(
+                    "│{}whatever{}│",
+                    helper::gen_whitespace(left),
+                    helper::gen_whitespace(right)
+                );
+                assert!(rendered.contains(&widest), "{}", rendered);
+                let narrow = format!(
+                    "│{}wh{}│",
+                    helper::gen_whitespace(left + 3),
+                    helper::gen_whitespace(right + 3)
+                );
+                assert!(rendered.contains(&narrow), "{}", rendered);
+            }
+        }
+    }
+
+    #[test]
+    fn test_asymmetric_padding() {
+        let expected = "┌───────────────┐\n\
+                        │               │\n\
+                        │ whatever      │\n\
+                        │ what          │\n\
+                        │               │\n\
+                        └───────────────┘";
+        let boxed_content = BoxBuilder::from("whatever\nwhat")
+            .padding_left(1)
+            .padding_right(6);
+        assert_eq!(expected, boxed_content.to_string());
+
+        let expected = "┌───────────────┐\n\
+                        │      whatever │\n\
+                        │          what │\n\
+                        └───────────────┘";
+        let boxed_content = BoxBuilder::from("whatever\nwhat")
+            .alignment(Alignment::Right)
+            .padding(0)
+            .padding_left(6)
+            .padding_right(1);
+        assert_eq!(expected, boxed_content.to_string());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_padding_same_width(
+            message in "\\PC*",
+            left in 0usize..10,
+            right in 0usize..10,
+            top in 0usize..3,
+            bottom in 0usize..3,
+        ) {
+            let boxed_content = BoxBuilder::new(message)
+                .padding_left(left)
+                .padding_right(right)
+                .padding_top(top)
+                .padding_bottom(bottom);
+            let rendered = boxed_content.to_string();
+            let width = helper::display_width(rendered.lines().next().unwrap());
+            for line in rendered.lines() {
+                prop_assert_eq!(helper::display_width(line), width);
+            }
+        }
+
+        #[test]
+        fn prop_to_string_total(
+            message in any::<String>(),
+            max_width in 0usize..200,
+            padding in 0usize..100,
+        ) {
+            BoxBuilder::new(message.clone()).max_width(max_width).padding(padding).to_string();
+            BoxBuilder::new(message)
+                .max_width(max_width)
+                .padding(padding)
+                .wrapping(Wrapping::Hard)
+                .to_string();
+        }
+
+        #[test]
+        fn prop_labels_in_narrow_box(
+            label in any::<String>(),
+            max_width in 0usize..12,
+        ) {
+            let boxed_content = BoxBuilder::from("ok")
+                .max_width(max_width)
+                .padding(0)
+                .title(&label)
+                .caption_left(&label)
+                .caption_center(&label)
+                .caption_right(&label);
+            let rendered = boxed_content.to_string();
+            let width = helper::display_width(rendered.lines().next().unwrap());
+            for line in rendered.lines() {
+                prop_assert_eq!(helper::display_width(line), width);
+            }
+        }
+
+        #[test]
+        fn prop_rows_same_width(
+            message in "\\PC*",
+            max_width in 0usize..120,
+            padding in 0usize..10,
+        ) {
+            let boxed_content = BoxBuilder::new(message).max_width(max_width).padding(padding);
+            let rendered = boxed_content.to_string();
+            let width = helper::display_width(rendered.lines().next().unwrap());
+            for line in rendered.lines() {
+                prop_assert_eq!(helper::display_width(line), width);
+            }
+        }
+    }
 }
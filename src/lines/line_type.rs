@@ -1,4 +1,5 @@
 /// Enumerated type used to change the line type of the box.
+#[derive(Clone, Copy, PartialEq)]
 pub enum LineType {
     /// Simple lines one line wide.
     /// ```text
@@ -47,4 +48,88 @@ pub enum LineType {
     /// ╚══════════════════════════════════════════════════════════╝
     /// ```
     Double,
+
+    /// Rounded corners
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────────╮
+    /// │ Lorem ipsum dolor sit amet, consectetur adipiscing elit, │
+    /// │ sed do eiusmod tempor incididunt ut labore et dolore     │
+    /// │ magna aliqua. Ut enim ad minim veniam, quis nostrud      │
+    /// │ exercitation ullamco laboris nisi ut aliquip ex ea       │
+    /// │ commodo consequat.                                       │
+    /// ╰──────────────────────────────────────────────────────────╯
+    /// ```
+    Rounded,
+
+    /// Plain ASCII for terminals without Unicode
+    /// ```text
+    /// +----------------------------------------------------------+
+    /// | Lorem ipsum dolor sit amet, consectetur adipiscing elit, |
+    /// | sed do eiusmod tempor incididunt ut labore et dolore     |
+    /// | magna aliqua. Ut enim ad minim veniam, quis nostrud      |
+    /// | exercitation ullamco laboris nisi ut aliquip ex ea       |
+    /// | commodo consequat.                                       |
+    /// +----------------------------------------------------------+
+    /// ```
+    Ascii,
+
+    /// Bolded lines broken into dashes
+    /// ```text
+    /// ┏┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┓
+    /// ┇ Lorem ipsum dolor sit amet, consectetur adipiscing elit, ┇
+    /// ┇ sed do eiusmod tempor incididunt ut labore et dolore     ┇
+    /// ┇ magna aliqua. Ut enim ad minim veniam, quis nostrud      ┇
+    /// ┇ exercitation ullamco laboris nisi ut aliquip ex ea       ┇
+    /// ┇ commodo consequat.                                       ┇
+    /// ┗┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┅┛
+    /// ```
+    HeavyDotted,
+
+    /// Bolded lines broken into long dashes
+    /// ```text
+    /// ┏╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍┓
+    /// ╏ Lorem ipsum dolor sit amet, consectetur adipiscing elit, ╏
+    /// ╏ sed do eiusmod tempor incididunt ut labore et dolore     ╏
+    /// ╏ magna aliqua. Ut enim ad minim veniam, quis nostrud      ╏
+    /// ╏ exercitation ullamco laboris nisi ut aliquip ex ea       ╏
+    /// ╏ commodo consequat.                                       ╏
+    /// ┗╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍╍┛
+    /// ```
+    DoubleDash,
+
+    /// Solid blocks
+    /// ```text
+    /// ████████████████████████████████████████████████████████████
+    /// █ Lorem ipsum dolor sit amet, consectetur adipiscing elit, █
+    /// █ sed do eiusmod tempor incididunt ut labore et dolore     █
+    /// █ magna aliqua. Ut enim ad minim veniam, quis nostrud      █
+    /// █ exercitation ullamco laboris nisi ut aliquip ex ea       █
+    /// █ commodo consequat.                                       █
+    /// ████████████████████████████████████████████████████████████
+    /// ```
+    Block,
+
+    /// Half blocks, hugging the content more tightly than Block
+    /// ```text
+    /// ▛▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▜
+    /// ▌ Lorem ipsum dolor sit amet, consectetur adipiscing elit, ▐
+    /// ▌ sed do eiusmod tempor incididunt ut labore et dolore     ▐
+    /// ▌ magna aliqua. Ut enim ad minim veniam, quis nostrud      ▐
+    /// ▌ exercitation ullamco laboris nisi ut aliquip ex ea       ▐
+    /// ▌ commodo consequat.                                       ▐
+    /// ▙▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▄▟
+    /// ```
+    HalfBlock,
+
+    /// Spaces only, so the box takes up the same room but isn't drawn
+    /// ```text
+    ///
+    ///   Lorem ipsum dolor sit amet, consectetur adipiscing elit,
+    ///   sed do eiusmod tempor incididunt ut labore et dolore
+    ///   magna aliqua. Ut enim ad minim veniam, quis nostrud
+    ///   exercitation ullamco laboris nisi ut aliquip ex ea
+    ///   commodo consequat.
+    ///
+    /// ```
+    Hidden,
 }
@@ -1,52 +1,283 @@
 pub mod line_type;
 
+use std::error::Error;
+use std::fmt;
+
+use crate::helper;
 use line_type::LineType;
 
 /// Map a LineType value to the appropriate type of lines that should ultimatly be displayed.
 pub fn resolve_line_type(line_type: line_type::LineType) -> Lines {
     match line_type {
-        LineType::Dotted => Lines {
-            horizontal: String::from("╌"),
-            vertical: String::from("╎"),
-            top_right: String::from("┐"),
-            top_left: String::from("┌"),
-            bottom_right: String::from("┘"),
-            bottom_left: String::from("└"),
-        },
-        LineType::Bold => Lines {
-            horizontal: String::from("━"),
-            vertical: String::from("┃"),
-            top_right: String::from("┓"),
-            top_left: String::from("┏"),
-            bottom_right: String::from("┛"),
-            bottom_left: String::from("┗"),
-        },
-        LineType::Double => Lines {
-            horizontal: String::from("═"),
-            vertical: String::from("║"),
-            top_right: String::from("╗"),
-            top_left: String::from("╔"),
-            bottom_right: String::from("╝"),
-            bottom_left: String::from("╚"),
-        },
-        LineType::Basic => Lines {
-            horizontal: String::from("─"),
-            vertical: String::from("│"),
-            top_right: String::from("┐"),
-            top_left: String::from("┌"),
-            bottom_right: String::from("┘"),
-            bottom_left: String::from("└"),
-        },
+        LineType::Dotted => {
+            Lines::from_glyphs("╌", "╎", ["┌", "┐", "└", "┘"], ["├", "┤", "┬", "┴", "┼"])
+        }
+        LineType::Bold => {
+            Lines::from_glyphs("━", "┃", ["┏", "┓", "┗", "┛"], ["┣", "┫", "┳", "┻", "╋"])
+        }
+        LineType::Double => {
+            Lines::from_glyphs("═", "║", ["╔", "╗", "╚", "╝"], ["╠", "╣", "╦", "╩", "╬"])
+        }
+        LineType::Basic => {
+            Lines::from_glyphs("─", "│", ["┌", "┐", "└", "┘"], ["├", "┤", "┬", "┴", "┼"])
+        }
+        LineType::Rounded => {
+            Lines::from_glyphs("─", "│", ["╭", "╮", "╰", "╯"], ["├", "┤", "┬", "┴", "┼"])
+        }
+        LineType::Ascii => {
+            Lines::from_glyphs("-", "|", ["+", "+", "+", "+"], ["+", "+", "+", "+", "+"])
+        }
+        LineType::HeavyDotted => {
+            Lines::from_glyphs("┅", "┇", ["┏", "┓", "┗", "┛"], ["┣", "┫", "┳", "┻", "╋"])
+        }
+        LineType::DoubleDash => {
+            Lines::from_glyphs("╍", "╏", ["┏", "┓", "┗", "┛"], ["┣", "┫", "┳", "┻", "╋"])
+        }
+        LineType::Block => {
+            Lines::from_glyphs("█", "█", ["█", "█", "█", "█"], ["█", "█", "█", "█", "█"])
+        }
+        // dividers are the upper and left halves, like the top and left lines they join
+        LineType::HalfBlock => {
+            Lines::from_glyphs("▀", "▌", ["▛", "▜", "▙", "▟"], ["▛", "▜", "▛", "▙", "▛"])
+                .bottom("▄")
+                .right("▐")
+        }
+        LineType::Hidden => {
+            Lines::from_glyphs(" ", " ", [" ", " ", " ", " "], [" ", " ", " ", " ", " "])
+        }
+    }
+}
+
+/// One side of a box
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// The line type of each side of a box
+pub struct Sides {
+    pub top: LineType,
+    pub bottom: LineType,
+    pub left: LineType,
+    pub right: LineType,
+}
+
+impl Sides {
+    /// Construct sides that all have the same line type
+    pub fn new(line_type: LineType) -> Sides {
+        Sides {
+            top: line_type,
+            bottom: line_type,
+            left: line_type,
+            right: line_type,
+        }
+    }
+
+    /// Build the lines for a box with these sides.
+    ///
+    /// Corners and junctions where two line types meet are picked from the
+    /// box drawing glyphs that join both weights, like `╒` for a double top
+    /// edge over a light left edge. Lines dividing the inside of the box
+    /// follow the top and left sides.
+    pub fn resolve(&self) -> Lines {
+        let top = resolve_line_type(self.top);
+        let bottom = resolve_line_type(self.bottom);
+        let left = resolve_line_type(self.left);
+        let right = resolve_line_type(self.right);
+        Lines {
+            top_left: join(self.top, self.left, &TOP_LEFT, |lines| &lines.top_left),
+            top_right: join(self.top, self.right, &TOP_RIGHT, |lines| &lines.top_right),
+            bottom_left: join(self.bottom, self.left, &BOTTOM_LEFT, |lines| {
+                &lines.bottom_left
+            }),
+            bottom_right: join(self.bottom, self.right, &BOTTOM_RIGHT, |lines| {
+                &lines.bottom_right
+            }),
+            left_tee: join(self.top, self.left, &LEFT_TEE, |lines| &lines.left_tee),
+            right_tee: join(self.top, self.right, &RIGHT_TEE, |lines| &lines.right_tee),
+            top_tee: join(self.top, self.left, &TOP_TEE, |lines| &lines.top_tee),
+            bottom_tee: join(self.bottom, self.left, &BOTTOM_TEE, |lines| {
+                &lines.bottom_tee
+            }),
+            cross: join(self.top, self.left, &CROSS, |lines| &lines.cross),
+            top: top.top,
+            bottom: bottom.bottom,
+            left: left.left,
+            right: right.right,
+            horizontal: top.horizontal,
+            vertical: left.vertical,
+        }
+    }
+
+    /// Set the line type of one side and redraw the pieces of `lines` along it.
+    ///
+    /// Only the pieces joined to that side are replaced: its edge, its corners,
+    /// and for the top and left sides the dividers inside the box that follow
+    /// them. The other pieces are kept, so custom lines stay custom elsewhere.
+    /// When that would no longer draw a rectangular box, such as one column wide
+    /// corners next to custom edges two columns wide, every piece is replaced.
+    pub fn set(&mut self, side: Side, line_type: LineType, lines: &mut Lines) {
+        match side {
+            Side::Top => self.top = line_type,
+            Side::Bottom => self.bottom = line_type,
+            Side::Left => self.left = line_type,
+            Side::Right => self.right = line_type,
+        }
+        let resolved = self.resolve();
+        let mut updated = lines.clone();
+        match side {
+            Side::Top => {
+                updated.top = resolved.top;
+                updated.top_left = resolved.top_left;
+                updated.top_right = resolved.top_right;
+                updated.left_tee = resolved.left_tee;
+                updated.right_tee = resolved.right_tee;
+                updated.top_tee = resolved.top_tee;
+                updated.cross = resolved.cross;
+                updated.horizontal = resolved.horizontal;
+            }
+            Side::Bottom => {
+                updated.bottom = resolved.bottom;
+                updated.bottom_left = resolved.bottom_left;
+                updated.bottom_right = resolved.bottom_right;
+                updated.bottom_tee = resolved.bottom_tee;
+            }
+            Side::Left => {
+                updated.left = resolved.left;
+                updated.top_left = resolved.top_left;
+                updated.bottom_left = resolved.bottom_left;
+                updated.left_tee = resolved.left_tee;
+                updated.top_tee = resolved.top_tee;
+                updated.bottom_tee = resolved.bottom_tee;
+                updated.cross = resolved.cross;
+                updated.vertical = resolved.vertical;
+            }
+            Side::Right => {
+                updated.right = resolved.right;
+                updated.top_right = resolved.top_right;
+                updated.bottom_right = resolved.bottom_right;
+                updated.right_tee = resolved.right_tee;
+            }
+        }
+        *lines = match updated.validate() {
+            Ok(()) => updated,
+            Err(_) => self.resolve(),
+        };
+    }
+}
+
+/// Weight of the box drawing lines of a line type
+#[derive(Clone, Copy)]
+enum Weight {
+    Light,
+    Heavy,
+    Double,
+}
+
+/// Line types that aren't drawn with box drawing lines have no weight
+fn weight(line_type: LineType) -> Option<Weight> {
+    match line_type {
+        LineType::Basic | LineType::Dotted | LineType::Rounded => Some(Weight::Light),
+        LineType::Bold | LineType::HeavyDotted | LineType::DoubleDash => Some(Weight::Heavy),
+        LineType::Double => Some(Weight::Double),
+        LineType::Ascii | LineType::Block | LineType::HalfBlock | LineType::Hidden => None,
+    }
+}
+
+// Glyphs joining a horizontal and a vertical line, indexed by
+// (horizontal, vertical) weight: light light, heavy light, light heavy,
+// heavy heavy, double light, light double, double double.
+const TOP_LEFT: [&str; 7] = ["┌", "┍", "┎", "┏", "╒", "╓", "╔"];
+const TOP_RIGHT: [&str; 7] = ["┐", "┑", "┒", "┓", "╕", "╖", "╗"];
+const BOTTOM_LEFT: [&str; 7] = ["└", "┕", "┖", "┗", "╘", "╙", "╚"];
+const BOTTOM_RIGHT: [&str; 7] = ["┘", "┙", "┚", "┛", "╛", "╜", "╝"];
+const LEFT_TEE: [&str; 7] = ["├", "┝", "┠", "┣", "╞", "╟", "╠"];
+const RIGHT_TEE: [&str; 7] = ["┤", "┥", "┨", "┫", "╡", "╢", "╣"];
+const TOP_TEE: [&str; 7] = ["┬", "┯", "┰", "┳", "╤", "╥", "╦"];
+const BOTTOM_TEE: [&str; 7] = ["┴", "┷", "┸", "┻", "╧", "╨", "╩"];
+const CROSS: [&str; 7] = ["┼", "┿", "╂", "╋", "╪", "╫", "╬"];
+
+/// Pick the glyph where a horizontal and a vertical line type meet.
+///
+/// Lines of the same type use their own glyph, and so do rounded lines meeting
+/// other light lines. Unicode has no glyphs joining
+/// heavy and double lines, so the vertical line is drawn light instead. When
+/// either line type isn't drawn with box drawing lines, the glyph of that
+/// line type is used as is.
+fn join(
+    horizontal: LineType,
+    vertical: LineType,
+    glyphs: &[&str; 7],
+    piece: fn(&Lines) -> &String,
+) -> String {
+    if horizontal == vertical {
+        return piece(&resolve_line_type(horizontal)).clone();
+    }
+    // rounded lines keep their rounded corners where they meet other light lines
+    let rounded = horizontal == LineType::Rounded || vertical == LineType::Rounded;
+    if let (true, Some(Weight::Light), Some(Weight::Light)) =
+        (rounded, weight(horizontal), weight(vertical))
+    {
+        return piece(&resolve_line_type(LineType::Rounded)).clone();
     }
+    let index = match (weight(horizontal), weight(vertical)) {
+        (None, _) => return piece(&resolve_line_type(horizontal)).clone(),
+        (_, None) => return piece(&resolve_line_type(vertical)).clone(),
+        (Some(Weight::Light), Some(Weight::Light)) => 0,
+        (Some(Weight::Heavy), Some(Weight::Light)) => 1,
+        (Some(Weight::Light), Some(Weight::Heavy)) => 2,
+        (Some(Weight::Heavy), Some(Weight::Heavy)) => 3,
+        (Some(Weight::Double), Some(Weight::Light)) => 4,
+        (Some(Weight::Light), Some(Weight::Double)) => 5,
+        (Some(Weight::Double), Some(Weight::Double)) => 6,
+        (Some(Weight::Heavy), Some(Weight::Double)) => 1,
+        (Some(Weight::Double), Some(Weight::Heavy)) => 4,
+    };
+    String::from(glyphs[index])
 }
 
+/// The set of pieces a box is drawn with.
+///
+/// Start from a [LineType](enum.LineType.html) and replace any of the pieces.
+/// Pieces may be more than one character or more than one column wide. Edges
+/// are repeated, and cut short if needed, to fill the length of each side.
+/// ```
+/// use bauxite::{BoxBuilder, LineType, Lines};
+///
+/// let rounded = Lines::from(LineType::Basic)
+///     .top_left("╭")
+///     .top_right("╮")
+///     .bottom_left("╰")
+///     .bottom_right("╯");
+/// let boxed_message = BoxBuilder::from("whatever").lines(rounded).unwrap();
+/// assert_eq!(
+///     boxed_message.to_string(),
+///     "╭────────────╮\n\
+///      │            │\n\
+///      │  whatever  │\n\
+///      │            │\n\
+///      ╰────────────╯"
+/// );
+/// ```
+#[derive(Clone)]
 pub struct Lines {
-    pub horizontal: String,
-    pub vertical: String,
-    pub top_right: String,
-    pub top_left: String,
-    pub bottom_right: String,
-    pub bottom_left: String,
+    pub(crate) top: String,
+    pub(crate) bottom: String,
+    pub(crate) left: String,
+    pub(crate) right: String,
+    pub(crate) top_left: String,
+    pub(crate) top_right: String,
+    pub(crate) bottom_left: String,
+    pub(crate) bottom_right: String,
+    pub(crate) horizontal: String,
+    pub(crate) vertical: String,
+    pub(crate) left_tee: String,
+    pub(crate) right_tee: String,
+    pub(crate) top_tee: String,
+    pub(crate) bottom_tee: String,
+    pub(crate) cross: String,
 }
 
 impl Lines {
@@ -54,4 +285,434 @@ impl Lines {
     pub fn new() -> Lines {
         resolve_line_type(LineType::Basic)
     }
+
+    /// Construct the Lines drawn for a [LineType](enum.LineType.html)
+    pub fn from(line_type: LineType) -> Lines {
+        resolve_line_type(line_type)
+    }
+
+    /// Build lines with the same piece on every edge.
+    /// Corners are top left, top right, bottom left, bottom right.
+    /// Junctions are left tee, right tee, top tee, bottom tee, cross.
+    fn from_glyphs(
+        horizontal: &str,
+        vertical: &str,
+        corners: [&str; 4],
+        junctions: [&str; 5],
+    ) -> Lines {
+        Lines {
+            top: String::from(horizontal),
+            bottom: String::from(horizontal),
+            left: String::from(vertical),
+            right: String::from(vertical),
+            top_left: String::from(corners[0]),
+            top_right: String::from(corners[1]),
+            bottom_left: String::from(corners[2]),
+            bottom_right: String::from(corners[3]),
+            horizontal: String::from(horizontal),
+            vertical: String::from(vertical),
+            left_tee: String::from(junctions[0]),
+            right_tee: String::from(junctions[1]),
+            top_tee: String::from(junctions[2]),
+            bottom_tee: String::from(junctions[3]),
+            cross: String::from(junctions[4]),
+        }
+    }
+
+    /// Set the edge repeated along the top of the box
+    pub fn top(mut self, piece: &str) -> Self {
+        self.top = String::from(piece);
+        self
+    }
+
+    /// Set the edge repeated along the bottom of the box
+    pub fn bottom(mut self, piece: &str) -> Self {
+        self.bottom = String::from(piece);
+        self
+    }
+
+    /// Set the edge drawn on the left of every row
+    pub fn left(mut self, piece: &str) -> Self {
+        self.left = String::from(piece);
+        self
+    }
+
+    /// Set the edge drawn on the right of every row
+    pub fn right(mut self, piece: &str) -> Self {
+        self.right = String::from(piece);
+        self
+    }
+
+    /// Set the top left corner
+    pub fn top_left(mut self, piece: &str) -> Self {
+        self.top_left = String::from(piece);
+        self
+    }
+
+    /// Set the top right corner
+    pub fn top_right(mut self, piece: &str) -> Self {
+        self.top_right = String::from(piece);
+        self
+    }
+
+    /// Set the bottom left corner
+    pub fn bottom_left(mut self, piece: &str) -> Self {
+        self.bottom_left = String::from(piece);
+        self
+    }
+
+    /// Set the bottom right corner
+    pub fn bottom_right(mut self, piece: &str) -> Self {
+        self.bottom_right = String::from(piece);
+        self
+    }
+
+    /// Set the piece repeated along lines that divide the inside of the box
+    pub fn horizontal(mut self, piece: &str) -> Self {
+        self.horizontal = String::from(piece);
+        self
+    }
+
+    /// Set the piece drawn for lines that divide the inside of the box into columns
+    pub fn vertical(mut self, piece: &str) -> Self {
+        self.vertical = String::from(piece);
+        self
+    }
+
+    /// Set the junction where a dividing line meets the left edge, like `├`
+    pub fn left_tee(mut self, piece: &str) -> Self {
+        self.left_tee = String::from(piece);
+        self
+    }
+
+    /// Set the junction where a dividing line meets the right edge, like `┤`
+    pub fn right_tee(mut self, piece: &str) -> Self {
+        self.right_tee = String::from(piece);
+        self
+    }
+
+    /// Set the junction where a dividing column meets the top edge, like `┬`
+    pub fn top_tee(mut self, piece: &str) -> Self {
+        self.top_tee = String::from(piece);
+        self
+    }
+
+    /// Set the junction where a dividing column meets the bottom edge, like `┴`
+    pub fn bottom_tee(mut self, piece: &str) -> Self {
+        self.bottom_tee = String::from(piece);
+        self
+    }
+
+    /// Set the junction where dividing lines cross, like `┼`
+    pub fn cross(mut self, piece: &str) -> Self {
+        self.cross = String::from(piece);
+        self
+    }
+
+    /// Check that boxes drawn with these lines are rectangular.
+    ///
+    /// Pieces stacked in the same column must be equally wide and pieces
+    /// repeated along a line must be at least one column wide.
+    pub fn validate(&self) -> Result<(), LinesError> {
+        let pieces = [
+            ("top", &self.top),
+            ("bottom", &self.bottom),
+            ("left", &self.left),
+            ("right", &self.right),
+            ("top_left", &self.top_left),
+            ("top_right", &self.top_right),
+            ("bottom_left", &self.bottom_left),
+            ("bottom_right", &self.bottom_right),
+            ("horizontal", &self.horizontal),
+            ("vertical", &self.vertical),
+            ("left_tee", &self.left_tee),
+            ("right_tee", &self.right_tee),
+            ("top_tee", &self.top_tee),
+            ("bottom_tee", &self.bottom_tee),
+            ("cross", &self.cross),
+        ];
+        for (name, piece) in pieces.iter() {
+            if piece.chars().any(char::is_control) {
+                return Err(LinesError::ControlCharacter(name));
+            }
+        }
+
+        for (name, piece) in pieces[..2].iter().chain(&pieces[8..9]) {
+            if helper::display_width(piece) == 0 {
+                return Err(LinesError::EmptyEdge(name));
+            }
+        }
+
+        let columns = [
+            [&pieces[2], &pieces[4], &pieces[6], &pieces[10]],
+            [&pieces[3], &pieces[5], &pieces[7], &pieces[11]],
+            [&pieces[9], &pieces[12], &pieces[13], &pieces[14]],
+        ];
+        for column in columns.iter() {
+            let (_, first) = column[0];
+            for (name, piece) in column[1..].iter() {
+                if helper::display_width(piece) != helper::display_width(first) {
+                    return Err(LinesError::UnevenColumn(name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Columns taken up by the left and right edges together
+    pub(crate) fn edge_width(&self) -> usize {
+        helper::display_width(&self.left) + helper::display_width(&self.right)
+    }
+}
+
+impl Default for Lines {
+    fn default() -> Lines {
+        Lines::new()
+    }
+}
+
+/// Reasons a set of [Lines](struct.Lines.html) can't draw a rectangular box.
+/// Each variant holds the name of the offending piece.
+#[derive(Debug, PartialEq)]
+pub enum LinesError {
+    /// A piece repeated along a line takes up no columns.
+    EmptyEdge(&'static str),
+    /// A piece is a different width from the pieces above and below it.
+    UnevenColumn(&'static str),
+    /// A piece contains a control character such as a newline or tab.
+    ControlCharacter(&'static str),
+}
+
+impl fmt::Display for LinesError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LinesError::EmptyEdge(name) => {
+                write!(formatter, "{} must be at least one column wide", name)
+            }
+            LinesError::UnevenColumn(name) => {
+                write!(
+                    formatter,
+                    "{} is not as wide as the pieces in line with it",
+                    name
+                )
+            }
+            LinesError::ControlCharacter(name) => {
+                write!(formatter, "{} contains a control character", name)
+            }
+        }
+    }
+}
+
+impl Error for LinesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_types_valid() {
+        for line_type in [
+            LineType::Basic,
+            LineType::Dotted,
+            LineType::Bold,
+            LineType::Double,
+        ] {
+            assert_eq!(Lines::from(line_type).validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_ascii_valid() {
+        let ascii = Lines::from(LineType::Basic)
+            .top("-")
+            .bottom("-")
+            .left("|")
+            .right("|")
+            .top_left("+")
+            .top_right("+")
+            .bottom_left("+")
+            .bottom_right("+");
+        assert_eq!(ascii.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_multi_column_valid() {
+        let wide = Lines::from(LineType::Basic)
+            .left("██")
+            .top_left("▛▀")
+            .bottom_left("▙▄")
+            .left_tee("█▌")
+            .top("=-");
+        assert_eq!(wide.validate(), Ok(()));
+        assert_eq!(wide.edge_width(), 3);
+    }
+
+    #[test]
+    fn test_uneven_column() {
+        let lines = Lines::from(LineType::Basic).left("||");
+        assert_eq!(lines.validate(), Err(LinesError::UnevenColumn("top_left")));
+        let lines = Lines::from(LineType::Basic).bottom_right("╯╯");
+        assert_eq!(
+            lines.validate(),
+            Err(LinesError::UnevenColumn("bottom_right"))
+        );
+        let lines = Lines::from(LineType::Basic).cross("++");
+        assert_eq!(lines.validate(), Err(LinesError::UnevenColumn("cross")));
+    }
+
+    #[test]
+    fn test_empty_edge() {
+        let lines = Lines::from(LineType::Basic).top("");
+        assert_eq!(lines.validate(), Err(LinesError::EmptyEdge("top")));
+        let lines = Lines::from(LineType::Basic).bottom("\u{301}");
+        assert_eq!(lines.validate(), Err(LinesError::EmptyEdge("bottom")));
+    }
+
+    #[test]
+    fn test_control_character() {
+        let lines = Lines::from(LineType::Basic).right("|\n");
+        assert_eq!(lines.validate(), Err(LinesError::ControlCharacter("right")));
+    }
+
+    #[test]
+    fn test_lines_error_display() {
+        let error = LinesError::UnevenColumn("top_left");
+        assert_eq!(
+            error.to_string(),
+            "top_left is not as wide as the pieces in line with it"
+        );
+    }
+
+    #[test]
+    fn test_sides_uniform() {
+        let lines = Sides::new(LineType::Rounded).resolve();
+        let expected = Lines::from(LineType::Rounded);
+        assert_eq!(lines.top_left, expected.top_left);
+        assert_eq!(lines.bottom_right, expected.bottom_right);
+        assert_eq!(lines.cross, expected.cross);
+    }
+
+    #[test]
+    fn test_set_side_keeps_other_pieces() {
+        let mut lines = Lines::from(LineType::Basic).bottom_left("╰");
+        let mut sides = Sides::new(LineType::Basic);
+        sides.set(Side::Top, LineType::Bold, &mut lines);
+        assert_eq!(lines.top, "━");
+        assert_eq!(lines.top_left, "┍");
+        assert_eq!(lines.bottom_left, "╰");
+    }
+
+    #[test]
+    fn test_set_side_replaces_uneven_lines() {
+        let mut lines = Lines::from(LineType::Ascii)
+            .left("||")
+            .top_left("+=")
+            .bottom_left("+=")
+            .left_tee("+=");
+        let mut sides = Sides::new(LineType::Basic);
+        sides.set(Side::Top, LineType::Double, &mut lines);
+        assert_eq!(lines.left, "│");
+        assert_eq!(lines.top_left, "╒");
+        assert_eq!(lines.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_sides_double_top() {
+        let mut sides = Sides::new(LineType::Basic);
+        sides.top = LineType::Double;
+        let lines = sides.resolve();
+        assert_eq!(lines.top, "═");
+        assert_eq!(lines.top_left, "╒");
+        assert_eq!(lines.top_right, "╕");
+        assert_eq!(lines.bottom_left, "└");
+        assert_eq!(lines.left_tee, "╞");
+        assert_eq!(lines.top_tee, "╤");
+        assert_eq!(lines.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_sides_heavy_left() {
+        let mut sides = Sides::new(LineType::Basic);
+        sides.left = LineType::Bold;
+        let lines = sides.resolve();
+        assert_eq!(lines.left, "┃");
+        assert_eq!(lines.top_left, "┎");
+        assert_eq!(lines.bottom_left, "┖");
+        assert_eq!(lines.top_right, "┐");
+        assert_eq!(lines.left_tee, "┠");
+    }
+
+    #[test]
+    fn test_sides_heavy_top_light_sides() {
+        let mut sides = Sides::new(LineType::Dotted);
+        sides.top = LineType::HeavyDotted;
+        let lines = sides.resolve();
+        assert_eq!(lines.top, "┅");
+        assert_eq!(lines.top_left, "┍");
+        assert_eq!(lines.top_right, "┑");
+    }
+
+    #[test]
+    fn test_sides_double_vertical() {
+        let mut sides = Sides::new(LineType::Basic);
+        sides.left = LineType::Double;
+        sides.right = LineType::Double;
+        let lines = sides.resolve();
+        assert_eq!(lines.top_left, "╓");
+        assert_eq!(lines.bottom_right, "╜");
+        assert_eq!(lines.right_tee, "╢");
+    }
+
+    #[test]
+    fn test_sides_heavy_and_double_fall_back() {
+        let mut sides = Sides::new(LineType::Bold);
+        sides.top = LineType::Double;
+        let lines = sides.resolve();
+        assert_eq!(lines.top_left, "╒");
+        assert_eq!(lines.bottom_left, "┗");
+
+        let mut sides = Sides::new(LineType::Double);
+        sides.top = LineType::Bold;
+        assert_eq!(sides.resolve().top_left, "┍");
+    }
+
+    #[test]
+    fn test_sides_without_weight_fall_back() {
+        let mut sides = Sides::new(LineType::Basic);
+        sides.top = LineType::Ascii;
+        let lines = sides.resolve();
+        assert_eq!(lines.top_left, "+");
+        assert_eq!(lines.bottom_left, "└");
+
+        let mut sides = Sides::new(LineType::Basic);
+        sides.left = LineType::Block;
+        let lines = sides.resolve();
+        assert_eq!(lines.top_left, "█");
+        assert_eq!(lines.top_right, "┐");
+    }
+
+    #[test]
+    fn test_sides_always_valid() {
+        let line_types = [
+            LineType::Basic,
+            LineType::Dotted,
+            LineType::Bold,
+            LineType::Double,
+            LineType::Rounded,
+            LineType::Ascii,
+            LineType::HeavyDotted,
+            LineType::DoubleDash,
+            LineType::Block,
+            LineType::HalfBlock,
+            LineType::Hidden,
+        ];
+        for top in line_types.iter() {
+            for left in line_types.iter() {
+                let mut sides = Sides::new(LineType::Basic);
+                sides.top = *top;
+                sides.left = *left;
+                assert_eq!(sides.resolve().validate(), Ok(()));
+            }
+        }
+    }
 }
@@ -0,0 +1,574 @@
+/// Tables drawn with the same lines and colors as boxes
+use std::fmt;
+
+use crate::ansi;
+use crate::color::{BoxColor, LineColor, Styles};
+use crate::formatting::{Alignment, Overflow};
+use crate::helper;
+use crate::lines::line_type::LineType;
+use crate::lines::{Lines, LinesError};
+use crate::wrap;
+use crate::{AnsiColorCode, RgbColor};
+
+/// Alignment and width limit of one column
+struct Column {
+    alignment: Alignment,
+    max_width: Option<usize>,
+}
+
+impl Column {
+    fn new() -> Column {
+        Column {
+            alignment: Alignment::Left,
+            max_width: None,
+        }
+    }
+}
+
+/// Table builder struct that represents your formatted table.
+///
+/// Rows may have different numbers of cells, missing cells are left empty.
+/// ```
+/// use bauxite::{Alignment, TableBuilder};
+///
+/// let table = TableBuilder::new()
+///     .header(&["Name", "Size"])
+///     .row(&["Cargo.toml", "512"])
+///     .row(&["README.md", "1024"])
+///     .column_alignment(1, Alignment::Right);
+/// assert_eq!(
+///     table.to_string(),
+///     "┌────────────┬──────┐\n\
+///      │ Name       │ Size │\n\
+///      ├────────────┼──────┤\n\
+///      │ Cargo.toml │  512 │\n\
+///      │ README.md  │ 1024 │\n\
+///      └────────────┴──────┘"
+/// );
+/// ```
+pub struct TableBuilder {
+    header: Option<Vec<String>>,
+    rows: Vec<Vec<String>>,
+    columns: Vec<Column>,
+    padding: usize,
+    overflow: Overflow,
+    ellipsis: String,
+    tab_width: usize,
+    lines: Lines,
+    color: BoxColor,
+}
+
+impl TableBuilder {
+    /// Create a new empty table
+    pub fn new() -> TableBuilder {
+        TableBuilder {
+            header: None,
+            rows: Vec::new(),
+            columns: Vec::new(),
+            padding: 1,
+            overflow: Overflow::Wrap,
+            ellipsis: String::from("…"),
+            tab_width: 8,
+            lines: Lines::new(),
+            color: BoxColor::new(),
+        }
+    }
+
+    /// Set the header row, drawn above the rows with a line under it
+    pub fn header(mut self, cells: &[&str]) -> Self {
+        self.header = Some(cells.iter().map(|cell| String::from(*cell)).collect());
+        self
+    }
+
+    /// Add a row to the bottom of the table
+    pub fn row(mut self, cells: &[&str]) -> Self {
+        self.rows
+            .push(cells.iter().map(|cell| String::from(*cell)).collect());
+        self
+    }
+
+    /// Set the alignment of the cells in a column, counting from zero.
+    /// Justified cells are left aligned.
+    pub fn column_alignment(mut self, column: usize, alignment: Alignment) -> Self {
+        self.column(column).alignment = alignment;
+        self
+    }
+
+    /// Set the widest a column's text may be, counting from zero.
+    ///
+    /// Wider text is wrapped or truncated depending on the
+    /// [Overflow](enum.Overflow.html) of the table.
+    pub fn column_max_width(mut self, column: usize, width: usize) -> Self {
+        self.column(column).max_width = Some(width.max(1));
+        self
+    }
+
+    /// Set what happens to cells wider than their column, they wrap by default
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Set the text left in place of what is cut off truncated cells, "…" by default
+    pub fn ellipsis(mut self, ellipsis: &str) -> Self {
+        self.ellipsis = String::from(ellipsis);
+        self
+    }
+
+    /// Set the number of columns between tab stops in cells, 8 by default.
+    /// Tab stops are counted from the start of the text in each cell.
+    pub fn tab_width(mut self, width: usize) -> Self {
+        self.tab_width = width;
+        self
+    }
+
+    /// Set the space either side of the text in every cell
+    pub fn padding(mut self, pad: usize) -> Self {
+        self.padding = pad;
+        self
+    }
+
+    /// Set the type of lines to draw using [LineType](enum.LineType.html)
+    pub fn line_type(mut self, line_type: LineType) -> Self {
+        self.lines = Lines::from(line_type);
+        self
+    }
+
+    /// Set the lines to draw the table with using [Lines](struct.Lines.html).
+    ///
+    /// Returns an error if the lines wouldn't make a rectangular table.
+    pub fn lines(mut self, lines: Lines) -> Result<Self, LinesError> {
+        lines.validate()?;
+        self.lines = lines;
+        Ok(self)
+    }
+
+    /// Sets 8 bit color code of the lines, see
+    /// [BoxBuilder::color_8](struct.BoxBuilder.html#method.color_8).
+    pub fn color_8(mut self, color: u8) -> Self {
+        self.color.border = LineColor::from_8(color);
+        self
+    }
+
+    /// Basic RGB colors of the lines.
+    pub fn color_rgb(mut self, red: u8, green: u8, blue: u8) -> Self {
+        self.color.border = LineColor::from_rgb(RgbColor { red, green, blue });
+        self
+    }
+
+    /// Simplest ANSI color codes of the lines.
+    pub fn color(mut self, code: AnsiColorCode) -> Self {
+        self.color.border = LineColor::from_ansi(code);
+        self
+    }
+
+    /// Sets 8 bit color code of the cell text.
+    pub fn text_color_8(mut self, color: u8) -> Self {
+        self.color.text = LineColor::from_8(color);
+        self
+    }
+
+    /// Basic RGB colors of the cell text.
+    pub fn text_color_rgb(mut self, red: u8, green: u8, blue: u8) -> Self {
+        self.color.text = LineColor::from_rgb(RgbColor { red, green, blue });
+        self
+    }
+
+    /// Simplest ANSI color codes of the cell text.
+    pub fn text_color(mut self, code: AnsiColorCode) -> Self {
+        self.color.text = LineColor::from_ansi(code);
+        self
+    }
+
+    /// Table to string.
+    /// Returns the full table with a line between the header and the rows
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        let mut table = String::new();
+        self.write_table(&mut table)
+            .expect("a String can always be written to");
+        table
+    }
+
+    /// Write the table into `out` a piece at a time, without building it as a String first
+    fn write_table<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let header = self.header.as_ref().map(|cells| self.fit_row(cells));
+        let rows = self
+            .rows
+            .iter()
+            .map(|cells| self.fit_row(cells))
+            .collect::<Vec<Vec<Vec<String>>>>();
+        let widths = column_widths(header.iter().chain(&rows));
+
+        let styles = self.color.styles();
+        let lines = &self.lines;
+        self.write_rule(
+            out,
+            &styles,
+            &widths,
+            [
+                &lines.top,
+                &lines.top_left,
+                &lines.top_tee,
+                &lines.top_right,
+            ],
+        )?;
+        out.write_char('\n')?;
+        if let Some(header) = &header {
+            self.write_row(out, &styles, &widths, header)?;
+            if !rows.is_empty() {
+                self.write_rule(
+                    out,
+                    &styles,
+                    &widths,
+                    [
+                        &lines.horizontal,
+                        &lines.left_tee,
+                        &lines.cross,
+                        &lines.right_tee,
+                    ],
+                )?;
+                out.write_char('\n')?;
+            }
+        }
+        for row in &rows {
+            self.write_row(out, &styles, &widths, row)?;
+        }
+        self.write_rule(
+            out,
+            &styles,
+            &widths,
+            [
+                &lines.bottom,
+                &lines.bottom_left,
+                &lines.bottom_tee,
+                &lines.bottom_right,
+            ],
+        )
+    }
+
+    /// Column settings, adding default columns up to `index` as needed
+    fn column(&mut self, index: usize) -> &mut Column {
+        while self.columns.len() <= index {
+            self.columns.push(Column::new());
+        }
+        &mut self.columns[index]
+    }
+
+    /// Split every cell of a row into the lines it is drawn with
+    fn fit_row(&self, cells: &[String]) -> Vec<Vec<String>> {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let max_width = self.columns.get(index).and_then(|column| column.max_width);
+                let mut lines = cell
+                    .lines()
+                    .map(|line| helper::expand_tabs(line, self.tab_width))
+                    .flat_map(|line| match (max_width, &self.overflow) {
+                        (None, _) => vec![line.into_owned()],
+                        (Some(width), Overflow::Wrap) => wrap::wrap_line(&line, width),
+                        (Some(width), overflow) => {
+                            vec![helper::truncate_with(
+                                &line,
+                                width,
+                                overflow,
+                                &self.ellipsis,
+                            )]
+                        }
+                    })
+                    .collect::<Vec<String>>();
+                ansi::carry_styles(lines.iter_mut());
+                lines
+            })
+            .collect()
+    }
+
+    /// Helper function to write a horizontal line across the table from its
+    /// edge, left end, junction and right end pieces
+    fn write_rule<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        widths: &[usize],
+        [edge, left, junction, right]: [&str; 4],
+    ) -> fmt::Result {
+        styles.write_border(out, |out| {
+            out.write_str(left)?;
+            for (index, width) in widths.iter().enumerate() {
+                if index > 0 {
+                    out.write_str(junction)?;
+                }
+                helper::write_repeated(out, edge, width + 2 * self.padding)?;
+            }
+            out.write_str(right)
+        })
+    }
+
+    /// Helper function to write the lines of one row, as tall as its tallest cell
+    fn write_row<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        widths: &[usize],
+        row: &[Vec<String>],
+    ) -> fmt::Result {
+        let height = row.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        for line in 0..height {
+            self.write_piece(out, styles, &self.lines.left)?;
+            for (index, width) in widths.iter().enumerate() {
+                if index > 0 {
+                    self.write_piece(out, styles, &self.lines.vertical)?;
+                }
+                let text = row
+                    .get(index)
+                    .and_then(|cell| cell.get(line))
+                    .map_or("", String::as_str);
+                self.write_cell(out, styles, index, text, *width)?;
+            }
+            self.write_piece(out, styles, &self.lines.right)?;
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    /// Helper function to write one piece of the lines between the cells
+    fn write_piece<W: fmt::Write>(&self, out: &mut W, styles: &Styles, piece: &str) -> fmt::Result {
+        if piece.is_empty() {
+            return Ok(());
+        }
+        styles.write_border(out, |out| out.write_str(piece))
+    }
+
+    /// Helper function to write one line of a cell, padded out to the width of its column
+    fn write_cell<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        styles: &Styles,
+        index: usize,
+        text: &str,
+        width: usize,
+    ) -> fmt::Result {
+        let space = width - helper::display_width(text);
+        let alignment = self.columns.get(index).map(|column| &column.alignment);
+        let left = match alignment {
+            Some(Alignment::Right) => space,
+            Some(Alignment::Center) => space / 2,
+            _ => 0,
+        };
+        let left_space = self.padding + left;
+        let right_space = self.padding + space - left;
+        if left_space + text.len() + right_space == 0 {
+            return Ok(());
+        }
+        styles.write_text(out, |out| {
+            helper::write_whitespace(out, left_space)?;
+            styles.write_message(out, text)?;
+            helper::write_whitespace(out, right_space)
+        })
+    }
+}
+
+/// Implement fmt for TableBuilder so we can use pass a TableBuilder to `println!` for printing
+impl fmt::Display for TableBuilder {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.write_table(formatter)
+    }
+}
+
+impl Default for TableBuilder {
+    fn default() -> Self {
+        TableBuilder::new()
+    }
+}
+
+/// Width of the widest line in each column
+fn column_widths<'a>(rows: impl Iterator<Item = &'a Vec<Vec<String>>>) -> Vec<usize> {
+    let mut widths = Vec::new();
+    for row in rows {
+        for (index, cell) in row.iter().enumerate() {
+            if widths.len() <= index {
+                widths.push(0);
+            }
+            let width = cell.iter().map(|line| helper::display_width(line)).max();
+            widths[index] = widths[index].max(width.unwrap_or(0));
+        }
+    }
+    widths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_and_rows() {
+        let expected = "┌───────┬─────┐\n\
+                        │ a     │ b   │\n\
+                        ├───────┼─────┤\n\
+                        │ one   │ two │\n\
+                        │ three │     │\n\
+                        └───────┴─────┘";
+        let table = TableBuilder::new()
+            .header(&["a", "b"])
+            .row(&["one", "two"])
+            .row(&["three"]);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_no_header() {
+        let expected = "┌───┬───┐\n\
+                        │ 1 │ 2 │\n\
+                        │ 3 │ 4 │\n\
+                        └───┴───┘";
+        let table = TableBuilder::new().row(&["1", "2"]).row(&["3", "4"]);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_column_alignment() {
+        let expected = "┌──────┬────────┬───────┐\n\
+                        │ left │ center │ right │\n\
+                        ├──────┼────────┼───────┤\n\
+                        │ a    │   b    │     c │\n\
+                        └──────┴────────┴───────┘";
+        let table = TableBuilder::new()
+            .header(&["left", "center", "right"])
+            .row(&["a", "b", "c"])
+            .column_alignment(1, Alignment::Center)
+            .column_alignment(2, Alignment::Right);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_wrap_column() {
+        let expected = "┌─────┬───────────┐\n\
+                        │ key │ value     │\n\
+                        ├─────┼───────────┤\n\
+                        │ a   │ Lorem     │\n\
+                        │     │ ipsum     │\n\
+                        │     │ dolor sit │\n\
+                        │ b   │ amet      │\n\
+                        └─────┴───────────┘";
+        let table = TableBuilder::new()
+            .header(&["key", "value"])
+            .row(&["a", "Lorem ipsum dolor sit"])
+            .row(&["b", "amet"])
+            .column_max_width(1, 10);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_truncate_column() {
+        let expected = "┌─────┬──────────┐\n\
+                        │ key │ value    │\n\
+                        ├─────┼──────────┤\n\
+                        │ a   │ Lorem i… │\n\
+                        └─────┴──────────┘";
+        let table = TableBuilder::new()
+            .header(&["key", "value"])
+            .row(&["a", "Lorem ipsum dolor sit"])
+            .column_max_width(1, 8)
+            .overflow(Overflow::Truncate);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_truncate_column_middle() {
+        let expected = "┌──────────┐\n\
+                        │ src...rs │\n\
+                        └──────────┘";
+        let table = TableBuilder::new()
+            .row(&["src/table.rs"])
+            .column_max_width(0, 8)
+            .overflow(Overflow::TruncateMiddle)
+            .ellipsis("...");
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_tab_in_cell() {
+        let expected = "┌───────────┬───┐\n\
+                        │ a   b     │ c │\n\
+                        │ abcd    e │ d │\n\
+                        └───────────┴───┘";
+        let table = TableBuilder::new()
+            .row(&["a\tb", "c"])
+            .row(&["abcd\te", "d"])
+            .tab_width(4);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_multiline_cell() {
+        let expected = "┌───┬───┐\n\
+                        │ a │ c │\n\
+                        │ b │   │\n\
+                        └───┴───┘";
+        let table = TableBuilder::new().row(&["a\nb", "c"]);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_double_cross() {
+        let expected = "╔═══╦═══╗\n\
+                        ║ a ║ b ║\n\
+                        ╠═══╬═══╣\n\
+                        ║ 1 ║ 2 ║\n\
+                        ╚═══╩═══╝";
+        let table = TableBuilder::new()
+            .line_type(LineType::Double)
+            .header(&["a", "b"])
+            .row(&["1", "2"]);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_bold_cross() {
+        let expected = "┏━━━┳━━━┓\n\
+                        ┃ a ┃ b ┃\n\
+                        ┣━━━╋━━━┫\n\
+                        ┃ 1 ┃ 2 ┃\n\
+                        ┗━━━┻━━━┛";
+        let table = TableBuilder::new()
+            .line_type(LineType::Bold)
+            .header(&["a", "b"])
+            .row(&["1", "2"]);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_wide_characters() {
+        let expected = "┌────────┬───┐\n\
+                        │ 日本語 │ 1 │\n\
+                        │ ab     │ 2 │\n\
+                        └────────┴───┘";
+        let table = TableBuilder::new().row(&["日本語", "1"]).row(&["ab", "2"]);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_colors() {
+        let table = TableBuilder::new()
+            .row(&["a"])
+            .color(AnsiColorCode::Red)
+            .text_color(AnsiColorCode::Blue);
+        let expected = "\x1B[31m┌───┐\x1B[0m\n\
+                        \x1B[31m│\x1B[0m\x1B[34m a \x1B[0m\x1B[31m│\x1B[0m\n\
+                        \x1B[31m└───┘\x1B[0m";
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn test_display() {
+        let table = TableBuilder::new().row(&["a"]);
+        assert_eq!(format!("{}", table), table.to_string());
+        let table = TableBuilder::new()
+            .header(&["a"])
+            .row(&["\x1B[1mb\x1B[0m c"])
+            .color(AnsiColorCode::Red)
+            .text_color(AnsiColorCode::Blue);
+        assert_eq!(format!("{}", table), table.to_string());
+    }
+}
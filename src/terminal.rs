@@ -0,0 +1,112 @@
+/// Width of the terminal the box is printed in
+use std::env;
+
+/// Columns assumed when the output isn't a terminal
+const DEFAULT_WIDTH: usize = 80;
+
+/// File descriptor of standard output
+const STDOUT: i32 = 1;
+
+/// Number of columns in the terminal on standard output.
+///
+/// The `COLUMNS` environment variable is used when it holds a width,
+/// otherwise the terminal is asked for its size. When standard output
+/// isn't a terminal the width is 80.
+pub fn width() -> usize {
+    width_of(STDOUT)
+}
+
+/// Number of columns in the terminal open on a file descriptor, found like `width`
+pub fn width_of(fd: i32) -> usize {
+    resolve_width(env::var("COLUMNS").ok().as_deref(), fd_width(fd))
+}
+
+/// Pick the width from the value of `COLUMNS`, or else from the size of the terminal
+fn resolve_width(columns: Option<&str>, terminal: Option<usize>) -> usize {
+    columns
+        .and_then(parse_columns)
+        .or(terminal)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+fn parse_columns(columns: &str) -> Option<usize> {
+    columns.trim().parse().ok().filter(|width| *width > 0)
+}
+
+/// Number of columns in the terminal open on a file descriptor, if it is one
+#[cfg(unix)]
+fn fd_width(fd: i32) -> Option<usize> {
+    // SAFETY: winsize is plain data, and TIOCGWINSZ only writes into it
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let result = unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) };
+    if result == 0 && size.ws_col > 0 {
+        Some(usize::from(size.ws_col))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn fd_width(_fd: i32) -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_width() {
+        assert_eq!(resolve_width(Some("120"), Some(42)), 120);
+        assert_eq!(resolve_width(Some("wide"), Some(42)), 42);
+        assert_eq!(resolve_width(None, Some(42)), 42);
+        assert_eq!(resolve_width(Some("0"), None), 80);
+        assert_eq!(resolve_width(None, None), 80);
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(parse_columns("120"), Some(120));
+        assert_eq!(parse_columns(" 40\n"), Some(40));
+        assert_eq!(parse_columns("0"), None);
+        assert_eq!(parse_columns("wide"), None);
+        assert_eq!(parse_columns(""), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_not_a_terminal() {
+        use std::os::unix::io::AsRawFd;
+
+        let file = std::fs::File::open("Cargo.toml").unwrap();
+        assert_eq!(fd_width(file.as_raw_fd()), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_pseudo_terminal() {
+        let mut master = 0;
+        let mut slave = 0;
+        let size = libc::winsize {
+            ws_row: 24,
+            ws_col: 42,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        let opened = unsafe {
+            libc::openpty(
+                &mut master,
+                &mut slave,
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                &size,
+            )
+        };
+        assert_eq!(opened, 0);
+        assert_eq!(fd_width(slave), Some(42));
+        unsafe {
+            libc::close(slave);
+            libc::close(master);
+        }
+    }
+}
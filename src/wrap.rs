@@ -0,0 +1,170 @@
+/// Word aware line wrapping
+use unicode_linebreak::linebreaks;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::ansi;
+use crate::helper::{display_width, split_at_width};
+
+/// Wrap a single line so that no wrapped line is wider than `width` columns.
+///
+/// Lines are broken at whitespace and the other break opportunities defined
+/// by the Unicode line breaking algorithm. Whitespace at a break is dropped.
+/// Words that are wider than `width` on their own are hyphenated where they
+/// split between two letters and broken hard everywhere else. Lines are never
+/// broken inside an escape sequence.
+pub fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    if display_width(line) <= width {
+        return vec![String::from(line)];
+    }
+
+    let mut wrapped = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+
+    let escapes = ansi::escape_ranges(line);
+    let breaks = linebreaks(line).filter(|(end, _)| {
+        !escapes
+            .iter()
+            .any(|range| range.start < *end && *end < range.end)
+    });
+    for (end, _) in breaks {
+        let word = &line[start..end];
+        start = end;
+
+        let candidate = current.clone() + word.trim_end();
+        if display_width(&candidate) <= width {
+            current += word;
+            continue;
+        }
+
+        if !current.trim_end().is_empty() {
+            wrapped.push(String::from(current.trim_end()));
+        }
+        current = break_word(word, width, &mut wrapped);
+    }
+
+    if !current.trim_end().is_empty() || wrapped.is_empty() {
+        wrapped.push(String::from(current.trim_end()));
+    }
+    wrapped
+}
+
+/// Break a word wider than `width` across as many lines as it takes.
+///
+/// Every full line is pushed onto `wrapped` and the remainder of the word,
+/// which still fits in `width`, is returned.
+fn break_word(word: &str, width: usize, wrapped: &mut Vec<String>) -> String {
+    let mut rest = word;
+    while display_width(rest.trim_end()) > width {
+        let (head, tail) = split_at_width(rest, width.saturating_sub(1));
+        if display_width(head) < width && ends_in_letter(head) && starts_with_letter(tail) {
+            wrapped.push(String::from(head) + "-");
+            rest = tail;
+        } else {
+            let (head, tail) = split_at_width(rest, width);
+            wrapped.push(String::from(head));
+            rest = tail;
+        }
+    }
+    String::from(rest)
+}
+
+fn ends_in_letter(text: &str) -> bool {
+    ansi::strip_escapes(text)
+        .graphemes(true)
+        .next_back()
+        .and_then(|grapheme| grapheme.chars().next())
+        .is_some_and(char::is_alphabetic)
+}
+
+fn starts_with_letter(text: &str) -> bool {
+    ansi::strip_escapes(text)
+        .chars()
+        .next()
+        .is_some_and(char::is_alphabetic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_line() {
+        assert_eq!(wrap_line("whatever", 10), vec!["whatever"]);
+    }
+
+    #[test]
+    fn test_empty_line() {
+        assert_eq!(wrap_line("", 10), vec![""]);
+    }
+
+    #[test]
+    fn test_break_at_whitespace() {
+        let wrapped = wrap_line("Lorem ipsum dolor sit amet", 12);
+        assert_eq!(wrapped, vec!["Lorem ipsum", "dolor sit", "amet"]);
+    }
+
+    #[test]
+    fn test_collapse_whitespace_at_break() {
+        let wrapped = wrap_line("Lorem     ipsum", 8);
+        assert_eq!(wrapped, vec!["Lorem", "ipsum"]);
+    }
+
+    #[test]
+    fn test_break_after_hyphen() {
+        let wrapped = wrap_line("well-known fact", 8);
+        assert_eq!(wrapped, vec!["well-", "known", "fact"]);
+    }
+
+    #[test]
+    fn test_break_between_ideographs() {
+        let wrapped = wrap_line("日本語の文章です", 6);
+        assert_eq!(wrapped, vec!["日本語", "の文章", "です"]);
+    }
+
+    #[test]
+    fn test_hyphenate_long_word() {
+        let wrapped = wrap_line("a pneumonoultramicroscopic word", 10);
+        assert_eq!(
+            wrapped,
+            vec!["a", "pneumonou-", "ltramicro-", "scopic", "word"]
+        );
+    }
+
+    #[test]
+    fn test_hard_break_long_token() {
+        let wrapped = wrap_line("0123456789012345", 8);
+        assert_eq!(wrapped, vec!["01234567", "89012345"]);
+    }
+
+    #[test]
+    fn test_wrap_colored_words() {
+        let line = "\x1B[31mLorem ipsum\x1B[0m \x1B]8;;https://a-b.c/d-e\x07dolor\x1B]8;;\x07 sit";
+        let wrapped = wrap_line(line, 6);
+        assert_eq!(
+            wrapped,
+            vec![
+                "\x1B[31mLorem",
+                "ipsum\x1B[0m",
+                "\x1B]8;;https://a-b.c/d-e\x07dolor\x1B]8;;\x07",
+                "sit"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hyphenate_colored_word() {
+        let wrapped = wrap_line("\x1B[1mabcdefgh\x1B[0m", 5);
+        assert_eq!(wrapped, vec!["\x1B[1mabcd-", "efgh\x1B[0m"]);
+    }
+
+    #[test]
+    fn test_wrapped_lines_fit() {
+        let line = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor";
+        for width in 1..30 {
+            for wrapped in wrap_line(line, width) {
+                assert!(display_width(&wrapped) <= width);
+            }
+        }
+    }
+}